@@ -0,0 +1,97 @@
+use bass_sys::*;
+
+// Not exported by bass-sys yet.
+const BASS_ATTRIB_VOLDSP: DWORD = 19;
+const BASS_ATTRIB_VOLDSP_PRIORITY: DWORD = 20;
+const BASS_ATTRIB_DOWNMIX: DWORD = 21;
+
+/// A channel attribute, see the `BASS_ATTRIB_*` constants.
+///
+/// `ScanInfo` and `User` are not floating-point values, they can only be accessed with `Stream::attribute_ex` and `Stream::set_attribute_ex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attribute {
+    Bitrate,
+    Buffer,
+    Cpu,
+    Downmix,
+    EaxMix,
+    Frequency,
+    Granule,
+    NetResume,
+    NoBuffer,
+    NoRamp,
+    Pan,
+    PushLimit,
+    ScanInfo,
+    SampleRateConversion,
+    Tail,
+    User,
+    Vbr,
+    Volume,
+    VolumeDsp,
+    VolumeDspPriority,
+    MusicActive,
+    MusicAmplify,
+    MusicBpm,
+    MusicPanSeparation,
+    MusicPositionScaler,
+    MusicSpeed,
+    MusicGlobalVolume,
+    /// The volume of a MOD music channel, channels are numbered from `0`.
+    MusicChannelVolume(u8),
+    /// The volume of a MOD music instrument, instruments are numbered from `0`.
+    MusicInstrumentVolume(u8),
+}
+
+impl Attribute {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            Attribute::Bitrate => BASS_ATTRIB_BITRATE,
+            Attribute::Buffer => BASS_ATTRIB_BUFFER,
+            Attribute::Cpu => BASS_ATTRIB_CPU,
+            Attribute::Downmix => BASS_ATTRIB_DOWNMIX,
+            Attribute::EaxMix => BASS_ATTRIB_EAXMIX,
+            Attribute::Frequency => BASS_ATTRIB_FREQ,
+            Attribute::Granule => BASS_ATTRIB_GRANULE,
+            Attribute::NetResume => BASS_ATTRIB_NET_RESUME,
+            Attribute::NoBuffer => BASS_ATTRIB_NOBUFFER,
+            Attribute::NoRamp => BASS_ATTRIB_NORAMP,
+            Attribute::Pan => BASS_ATTRIB_PAN,
+            Attribute::PushLimit => BASS_ATTRIB_PUSH_LIMIT,
+            Attribute::ScanInfo => BASS_ATTRIB_SCANINFO,
+            Attribute::SampleRateConversion => BASS_ATTRIB_SRC,
+            Attribute::Tail => BASS_ATTRIB_TAIL,
+            Attribute::User => BASS_ATTRIB_USER,
+            Attribute::Vbr => BASS_ATTRIB_VBR,
+            Attribute::Volume => BASS_ATTRIB_VOL,
+            Attribute::VolumeDsp => BASS_ATTRIB_VOLDSP,
+            Attribute::VolumeDspPriority => BASS_ATTRIB_VOLDSP_PRIORITY,
+            Attribute::MusicActive => BASS_ATTRIB_MUSIC_ACTIVE,
+            Attribute::MusicAmplify => BASS_ATTRIB_MUSIC_AMPLIFY,
+            Attribute::MusicBpm => BASS_ATTRIB_MUSIC_BPM,
+            Attribute::MusicPanSeparation => BASS_ATTRIB_MUSIC_PANSEP,
+            Attribute::MusicPositionScaler => BASS_ATTRIB_MUSIC_PSCALER,
+            Attribute::MusicSpeed => BASS_ATTRIB_MUSIC_SPEED,
+            Attribute::MusicGlobalVolume => BASS_ATTRIB_MUSIC_VOL_GLOBAL,
+            Attribute::MusicChannelVolume(channel) => {
+                BASS_ATTRIB_MUSIC_VOL_CHAN + DWORD::from(*channel)
+            }
+            Attribute::MusicInstrumentVolume(instrument) => {
+                BASS_ATTRIB_MUSIC_VOL_INST + DWORD::from(*instrument)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn music_volumes_stay_in_their_ranges() {
+        assert_eq!(Attribute::MusicChannelVolume(0).raw(), 0x200);
+        assert_eq!(Attribute::MusicChannelVolume(u8::MAX).raw(), 0x2FF);
+        assert_eq!(Attribute::MusicInstrumentVolume(0).raw(), 0x300);
+        assert_eq!(Attribute::MusicInstrumentVolume(u8::MAX).raw(), 0x3FF);
+    }
+}
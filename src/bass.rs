@@ -0,0 +1,66 @@
+use crate::{BassError, Device};
+
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::Arc;
+
+use bass_sys::*;
+
+struct Context {
+    device: DWORD,
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        BASS_SetDevice(self.device);
+        BASS_Free();
+    }
+}
+
+/// An initialized output device.
+///
+/// The device is freed once the last `Bass` referring to it (including the ones held by streams) is dropped.
+#[derive(Clone)]
+pub struct Bass {
+    context: Arc<Context>,
+}
+
+impl Bass {
+    /// Initializes the output device.
+    ///
+    /// `device` is `-1` for the default device, `0` for the "no sound" device and `1..` for real devices.
+    /// `flags` is a combination of the `BASS_DEVICE_*` flags.
+    pub fn init(
+        device: i32,
+        sample_rate: u32,
+        flags: u32,
+        window: *mut c_void,
+    ) -> Result<Bass, BassError> {
+        if BASS_Init(device, sample_rate, flags, window, ptr::null_mut()) == 0 {
+            return Err(BassError::last());
+        }
+
+        let device = BASS_GetDevice();
+
+        Ok(Bass {
+            context: Arc::new(Context { device }),
+        })
+    }
+
+    pub fn get_device_number(&self) -> u32 {
+        self.context.device
+    }
+
+    pub fn get_device(&self) -> Result<Device, BassError> {
+        Device::get(self.context.device)
+    }
+
+    /// Makes this device the current one for the calling thread.
+    pub fn make_current(&self) -> Result<(), BassError> {
+        if BASS_SetDevice(self.context.device) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::SampleFormat;
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+#[cfg(target_os = "windows")]
+use widestring::U16CStr;
+
+use bass_sys::*;
+
+// Add-on types, not exported by bass-sys.
+const BASS_CTYPE_STREAM_WMA: DWORD = 0x10300;
+const BASS_CTYPE_STREAM_WMA_MP3: DWORD = 0x10301;
+const BASS_CTYPE_STREAM_WV: DWORD = 0x10500;
+const BASS_CTYPE_STREAM_APE: DWORD = 0x10700;
+const BASS_CTYPE_STREAM_FLAC: DWORD = 0x10900;
+const BASS_CTYPE_STREAM_FLAC_OGG: DWORD = 0x10901;
+const BASS_CTYPE_STREAM_AAC: DWORD = 0x10B00;
+const BASS_CTYPE_STREAM_MP4: DWORD = 0x10B01;
+const BASS_CTYPE_STREAM_MIDI: DWORD = 0x10D00;
+const BASS_CTYPE_STREAM_ALAC: DWORD = 0x10E00;
+const BASS_CTYPE_STREAM_OPUS: DWORD = 0x11200;
+const BASS_CTYPE_STREAM_DSD: DWORD = 0x11700;
+
+/// The type of a channel, see the `BASS_CTYPE_*` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelType {
+    Sample,
+    Record,
+    /// A stream created with a callback or a push stream.
+    Stream,
+    Ogg,
+    Mp1,
+    Mp2,
+    Mp3,
+    Aiff,
+    CoreAudio,
+    MediaFoundation,
+    AndroidMedia,
+    StreamSample,
+    Dummy,
+    Device,
+    WavPcm,
+    WavFloat,
+    /// A WAV file with another codec, the value is its format tag.
+    Wav(u16),
+    Mod,
+    Mtm,
+    S3m,
+    Xm,
+    It,
+    Wma,
+    WmaMp3,
+    WavPack,
+    Ape,
+    Flac,
+    FlacOgg,
+    Aac,
+    Mp4,
+    Midi,
+    Alac,
+    Opus,
+    Dsd,
+    /// A type added by a plugin that isn't known here.
+    Plugin(u32),
+}
+
+impl ChannelType {
+    pub fn from_raw(raw: DWORD) -> ChannelType {
+        if raw & BASS_CTYPE_MUSIC_MOD != 0 {
+            return match raw & !BASS_CTYPE_MUSIC_MO3 {
+                BASS_CTYPE_MUSIC_MTM => ChannelType::Mtm,
+                BASS_CTYPE_MUSIC_S3M => ChannelType::S3m,
+                BASS_CTYPE_MUSIC_XM => ChannelType::Xm,
+                BASS_CTYPE_MUSIC_IT => ChannelType::It,
+                _ => ChannelType::Mod,
+            };
+        }
+
+        match raw {
+            BASS_CTYPE_SAMPLE => ChannelType::Sample,
+            BASS_CTYPE_RECORD => ChannelType::Record,
+            BASS_CTYPE_STREAM => ChannelType::Stream,
+            BASS_CTYPE_STREAM_OGG => ChannelType::Ogg,
+            BASS_CTYPE_STREAM_MP1 => ChannelType::Mp1,
+            BASS_CTYPE_STREAM_MP2 => ChannelType::Mp2,
+            BASS_CTYPE_STREAM_MP3 => ChannelType::Mp3,
+            BASS_CTYPE_STREAM_AIFF => ChannelType::Aiff,
+            BASS_CTYPE_STREAM_CA => ChannelType::CoreAudio,
+            BASS_CTYPE_STREAM_MF => ChannelType::MediaFoundation,
+            BASS_CTYPE_STREAM_AM => ChannelType::AndroidMedia,
+            BASS_CTYPE_STREAM_SAMPLE => ChannelType::StreamSample,
+            BASS_CTYPE_STREAM_DUMMY => ChannelType::Dummy,
+            BASS_CTYPE_STREAM_DEVICE => ChannelType::Device,
+            BASS_CTYPE_STREAM_WAV_PCM => ChannelType::WavPcm,
+            BASS_CTYPE_STREAM_WAV_FLOAT => ChannelType::WavFloat,
+            BASS_CTYPE_STREAM_WMA => ChannelType::Wma,
+            BASS_CTYPE_STREAM_WMA_MP3 => ChannelType::WmaMp3,
+            BASS_CTYPE_STREAM_WV => ChannelType::WavPack,
+            BASS_CTYPE_STREAM_APE => ChannelType::Ape,
+            BASS_CTYPE_STREAM_FLAC => ChannelType::Flac,
+            BASS_CTYPE_STREAM_FLAC_OGG => ChannelType::FlacOgg,
+            BASS_CTYPE_STREAM_AAC => ChannelType::Aac,
+            BASS_CTYPE_STREAM_MP4 => ChannelType::Mp4,
+            BASS_CTYPE_STREAM_MIDI => ChannelType::Midi,
+            BASS_CTYPE_STREAM_ALAC => ChannelType::Alac,
+            BASS_CTYPE_STREAM_OPUS => ChannelType::Opus,
+            BASS_CTYPE_STREAM_DSD => ChannelType::Dsd,
+            _ if raw & BASS_CTYPE_STREAM_WAV != 0 => ChannelType::Wav(raw as u16),
+            _ => ChannelType::Plugin(raw),
+        }
+    }
+}
+
+/// Information about a channel, as reported by `BASS_ChannelGetInfo`.
+#[derive(Debug, Clone)]
+pub struct ChannelInfo {
+    frequency: u32,
+    channels: u32,
+    flags: DWORD,
+    channel_type: DWORD,
+    original_resolution: u32,
+    plugin: HPLUGIN,
+    file_name: Option<String>,
+}
+
+impl ChannelInfo {
+    pub(crate) fn from_raw(info: &BassChannelInfo) -> ChannelInfo {
+        ChannelInfo {
+            frequency: info.default_frequency,
+            channels: info.channels,
+            flags: info.flags,
+            channel_type: info.type_of_channel,
+            original_resolution: info.original_resolution,
+            plugin: info.plugin,
+            file_name: file_name_from_raw(info.file_name, info.flags),
+        }
+    }
+
+    pub fn get_frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    pub fn get_channels(&self) -> u32 {
+        self.channels
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_type(&self) -> ChannelType {
+        ChannelType::from_raw(self.channel_type)
+    }
+
+    /// Returns the sample format the channel's data is produced in.
+    pub fn get_sample_format(&self) -> SampleFormat {
+        if self.flags & BASS_SAMPLE_FLOAT != 0 {
+            SampleFormat::Float
+        } else if self.flags & BASS_SAMPLE_8BITS != 0 {
+            SampleFormat::Int8
+        } else {
+            SampleFormat::Int16
+        }
+    }
+
+    /// Returns the resolution of the source in bits, if it's known.
+    pub fn get_original_resolution(&self) -> Option<u32> {
+        match self.original_resolution {
+            0 => None,
+            resolution => Some(resolution),
+        }
+    }
+
+    /// Returns the plugin handling the channel, or `None` if it's handled by BASS itself.
+    pub fn get_plugin(&self) -> Option<HPLUGIN> {
+        match self.plugin {
+            0 => None,
+            plugin => Some(plugin),
+        }
+    }
+
+    /// Returns the file the stream was created from, `None` for other kinds of streams.
+    pub fn get_file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    pub fn is_decoding(&self) -> bool {
+        self.flags & BASS_STREAM_DECODE != 0
+    }
+
+    pub fn is_mo3(&self) -> bool {
+        self.channel_type & BASS_CTYPE_MUSIC_MOD != 0
+            && self.channel_type & BASS_CTYPE_MUSIC_MO3 != 0
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn file_name_from_raw(raw: *const c_char, flags: DWORD) -> Option<String> {
+    if raw.is_null() {
+        return None;
+    }
+
+    if flags & BASS_UNICODE != 0 {
+        let raw = unsafe { U16CStr::from_ptr_str(raw as *const u16) };
+
+        return Some(raw.to_string_lossy());
+    }
+
+    let raw = unsafe { CStr::from_ptr(raw) };
+
+    Some(raw.to_string_lossy().into_owned())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn file_name_from_raw(raw: *const c_char, _flags: DWORD) -> Option<String> {
+    if raw.is_null() {
+        return None;
+    }
+
+    let raw = unsafe { CStr::from_ptr(raw) };
+
+    Some(raw.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    #[test]
+    fn types_from_raw() {
+        let cases = [
+            (0x1, ChannelType::Sample),
+            (0x10000, ChannelType::Stream),
+            (0x10005, ChannelType::Mp3),
+            (0x18000, ChannelType::Dummy),
+            (0x20000, ChannelType::Mod),
+            (0x20100, ChannelType::Mod),
+            (0x20003, ChannelType::Xm),
+            (0x20104, ChannelType::It),
+            (0x50001, ChannelType::WavPcm),
+            (0x50003, ChannelType::WavFloat),
+            (0x40055, ChannelType::Wav(0x55)),
+            (0x10900, ChannelType::Flac),
+            (0x11200, ChannelType::Opus),
+            (0x12345, ChannelType::Plugin(0x12345)),
+        ];
+
+        for (raw, channel_type) in cases {
+            assert_eq!(ChannelType::from_raw(raw), channel_type, "type {:#x}", raw);
+        }
+    }
+
+    #[test]
+    fn mo3_only_for_music() {
+        let info = |channel_type| {
+            ChannelInfo::from_raw(&BassChannelInfo::new(
+                44100,
+                2,
+                0,
+                channel_type,
+                0,
+                0,
+                0,
+                ptr::null(),
+            ))
+        };
+
+        assert!(info(0x20104).is_mo3());
+        assert!(!info(0x20004).is_mo3());
+        assert!(!info(0x10100).is_mo3());
+    }
+}
@@ -0,0 +1,27 @@
+use bass_sys::*;
+
+/// The playback state of a channel, as reported by `BASS_ChannelIsActive`.
+///
+/// Decoding channels are `Playing` until the end is reached, then they're `Stopped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelState {
+    Stopped,
+    Playing,
+    Paused,
+    /// Paused because the device was stopped or failed, it resumes along with the device.
+    PausedDevice,
+    /// Playback stalled because the stream ran out of data, it resumes once there's enough.
+    Stalled,
+}
+
+impl ChannelState {
+    pub fn from_raw(raw: DWORD) -> ChannelState {
+        match raw {
+            BASS_ACTIVE_PLAYING => ChannelState::Playing,
+            BASS_ACTIVE_STALLED => ChannelState::Stalled,
+            BASS_ACTIVE_PAUSED => ChannelState::Paused,
+            BASS_ACTIVE_PAUSED_DEVICE => ChannelState::PausedDevice,
+            _ => ChannelState::Stopped,
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::BassError;
+
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+use std::time::Duration;
+
+use bass_sys::*;
+
+/// Typed access to the global `BASS_CONFIG_*` options.
+pub struct Config;
+
+impl Config {
+    pub fn get_buffer_length() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_BUFFER)
+    }
+
+    pub fn set_buffer_length(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_BUFFER, value)
+    }
+
+    pub fn get_update_period() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_UPDATEPERIOD)
+    }
+
+    pub fn set_update_period(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_UPDATEPERIOD, value)
+    }
+
+    pub fn get_update_threads() -> Result<u32, BassError> {
+        get(BASS_CONFIG_UPDATETHREADS)
+    }
+
+    pub fn set_update_threads(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_UPDATETHREADS, value)
+    }
+
+    pub fn get_device_buffer_length() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_DEV_BUFFER)
+    }
+
+    pub fn set_device_buffer_length(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_DEV_BUFFER, value)
+    }
+
+    pub fn get_device_period() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_DEV_PERIOD)
+    }
+
+    pub fn set_device_period(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_DEV_PERIOD, value)
+    }
+
+    /// Whether the output follows the system's default device when it changes (Windows and macOS).
+    pub fn get_device_default() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_DEV_DEFAULT)
+    }
+
+    pub fn set_device_default(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_DEV_DEFAULT, value)
+    }
+
+    pub fn get_device_nonstop() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_DEV_NONSTOP)
+    }
+
+    pub fn set_device_nonstop(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_DEV_NONSTOP, value)
+    }
+
+    pub fn get_float_dsp() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_FLOATDSP)
+    }
+
+    pub fn set_float_dsp(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_FLOATDSP, value)
+    }
+
+    /// Whether floating-point sample data is supported on this platform, it's read-only.
+    pub fn is_float_supported() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_FLOAT)
+    }
+
+    /// The default sample rate conversion quality, from `0` (linear interpolation) to `4` (512 point sinc).
+    pub fn get_sample_rate_conversion_quality() -> Result<u32, BassError> {
+        get(BASS_CONFIG_SRC)
+    }
+
+    pub fn set_sample_rate_conversion_quality(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_SRC, value)
+    }
+
+    /// The default sample rate conversion quality of sample channels, from `0` (linear interpolation) to `4` (512 point sinc).
+    pub fn get_sample_channel_src_quality() -> Result<u32, BassError> {
+        get(BASS_CONFIG_SRC_SAMPLE)
+    }
+
+    pub fn set_sample_channel_src_quality(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_SRC_SAMPLE, value)
+    }
+
+    /// Whether strings are UTF-16 instead of ANSI (Windows only).
+    pub fn get_unicode() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_UNICODE)
+    }
+
+    pub fn set_unicode(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_UNICODE, value)
+    }
+
+    pub fn get_pause_no_play() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_PAUSE_NOPLAY)
+    }
+
+    pub fn set_pause_no_play(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_PAUSE_NOPLAY, value)
+    }
+
+    /// The global stream volume, from `0` (silent) to `10000` (full).
+    pub fn get_global_stream_volume() -> Result<u32, BassError> {
+        get(BASS_CONFIG_GVOL_STREAM)
+    }
+
+    pub fn set_global_stream_volume(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_GVOL_STREAM, value)
+    }
+
+    pub fn get_global_sample_volume() -> Result<u32, BassError> {
+        get(BASS_CONFIG_GVOL_SAMPLE)
+    }
+
+    pub fn set_global_sample_volume(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_GVOL_SAMPLE, value)
+    }
+
+    pub fn get_global_music_volume() -> Result<u32, BassError> {
+        get(BASS_CONFIG_GVOL_MUSIC)
+    }
+
+    pub fn set_global_music_volume(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_GVOL_MUSIC, value)
+    }
+
+    /// Whether volume is translated logarithmically instead of linearly.
+    pub fn get_logarithmic_volume_curve() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_CURVE_VOL)
+    }
+
+    pub fn set_logarithmic_volume_curve(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_CURVE_VOL, value)
+    }
+
+    pub fn get_logarithmic_panning_curve() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_CURVE_PAN)
+    }
+
+    pub fn set_logarithmic_panning_curve(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_CURVE_PAN, value)
+    }
+
+    /// The amount of data scanned to verify file formats, in bytes.
+    pub fn get_verification_length() -> Result<u32, BassError> {
+        get(BASS_CONFIG_VERIFY)
+    }
+
+    pub fn set_verification_length(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_VERIFY, value)
+    }
+
+    pub fn get_net_verification_length() -> Result<u32, BassError> {
+        get(BASS_CONFIG_VERIFY_NET)
+    }
+
+    pub fn set_net_verification_length(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_VERIFY_NET, value)
+    }
+
+    pub fn get_ogg_prescan() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_OGG_PRESCAN)
+    }
+
+    pub fn set_ogg_prescan(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_OGG_PRESCAN, value)
+    }
+
+    pub fn get_async_file_buffer_length() -> Result<u32, BassError> {
+        get(BASS_CONFIG_ASYNCFILE_BUFFER)
+    }
+
+    pub fn set_async_file_buffer_length(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_ASYNCFILE_BUFFER, value)
+    }
+
+    /// The number of existing handles, it's read-only.
+    pub fn get_handle_count() -> Result<u32, BassError> {
+        get(BASS_CONFIG_HANDLES)
+    }
+
+    pub fn get_net_timeout() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_NET_TIMEOUT)
+    }
+
+    pub fn set_net_timeout(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_NET_TIMEOUT, value)
+    }
+
+    pub fn get_net_read_timeout() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_NET_READTIMEOUT)
+    }
+
+    pub fn set_net_read_timeout(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_NET_READTIMEOUT, value)
+    }
+
+    pub fn get_net_buffer_length() -> Result<Duration, BassError> {
+        get_duration(BASS_CONFIG_NET_BUFFER)
+    }
+
+    pub fn set_net_buffer_length(value: Duration) -> Result<(), BassError> {
+        set_duration(BASS_CONFIG_NET_BUFFER, value)
+    }
+
+    /// The amount of the download buffer to fill before playback starts, in percents.
+    pub fn get_net_prebuffer() -> Result<u32, BassError> {
+        get(BASS_CONFIG_NET_PREBUF)
+    }
+
+    pub fn set_net_prebuffer(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_NET_PREBUF, value)
+    }
+
+    pub fn get_net_prebuffer_wait() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_NET_PREBUF_WAIT)
+    }
+
+    pub fn set_net_prebuffer_wait(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_NET_PREBUF_WAIT, value)
+    }
+
+    pub fn get_net_passive() -> Result<bool, BassError> {
+        get_bool(BASS_CONFIG_NET_PASSIVE)
+    }
+
+    pub fn set_net_passive(value: bool) -> Result<(), BassError> {
+        set_bool(BASS_CONFIG_NET_PASSIVE, value)
+    }
+
+    /// When playlists are processed: `0` never, `1` in `Stream::create_from_url` only, `2` also in `Stream::create_from_file`.
+    pub fn get_net_playlist() -> Result<u32, BassError> {
+        get(BASS_CONFIG_NET_PLAYLIST)
+    }
+
+    pub fn set_net_playlist(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_NET_PLAYLIST, value)
+    }
+
+    pub fn get_net_playlist_depth() -> Result<u32, BassError> {
+        get(BASS_CONFIG_NET_PLAYLIST_DEPTH)
+    }
+
+    pub fn set_net_playlist_depth(value: u32) -> Result<(), BassError> {
+        set(BASS_CONFIG_NET_PLAYLIST_DEPTH, value)
+    }
+
+    pub fn get_net_agent() -> Result<Option<String>, BassError> {
+        get_string(BASS_CONFIG_NET_AGENT)
+    }
+
+    pub fn set_net_agent(value: Option<&str>) -> Result<(), BassError> {
+        set_string(BASS_CONFIG_NET_AGENT, value)
+    }
+
+    /// The proxy server in the `user:pass@server:port` format, `None` disables the proxy.
+    pub fn get_net_proxy() -> Result<Option<String>, BassError> {
+        get_string(BASS_CONFIG_NET_PROXY)
+    }
+
+    pub fn set_net_proxy(value: Option<&str>) -> Result<(), BassError> {
+        set_string(BASS_CONFIG_NET_PROXY, value)
+    }
+}
+
+fn get(option: DWORD) -> Result<u32, BassError> {
+    let value = BASS_GetConfig(option);
+
+    if value == DWORD::MAX {
+        match BASS_ErrorGetCode() {
+            BASS_OK => {}
+            error_code => return Err(BassError::from_code(error_code)),
+        }
+    }
+
+    Ok(value)
+}
+
+fn set(option: DWORD, value: u32) -> Result<(), BassError> {
+    if BASS_SetConfig(option, value) == 0 {
+        return Err(BassError::last());
+    }
+
+    Ok(())
+}
+
+fn get_bool(option: DWORD) -> Result<bool, BassError> {
+    get(option).map(|value| value != 0)
+}
+
+fn set_bool(option: DWORD, value: bool) -> Result<(), BassError> {
+    set(option, value as u32)
+}
+
+fn get_duration(option: DWORD) -> Result<Duration, BassError> {
+    get(option).map(|value| Duration::from_millis(value as u64))
+}
+
+fn set_duration(option: DWORD, value: Duration) -> Result<(), BassError> {
+    if value.as_millis() > u32::MAX as u128 {
+        return Err(BassError::ValueOutOfRange);
+    }
+
+    set(option, value.as_millis() as u32)
+}
+
+fn get_string(option: DWORD) -> Result<Option<String>, BassError> {
+    let value = BASS_GetConfigPtr(option);
+
+    if value.is_null() {
+        match BASS_ErrorGetCode() {
+            BASS_OK => return Ok(None),
+            error_code => return Err(BassError::from_code(error_code)),
+        }
+    }
+
+    let value = unsafe { CStr::from_ptr(value as *const c_char) };
+
+    Ok(Some(value.to_string_lossy().into_owned()))
+}
+
+fn set_string(option: DWORD, value: Option<&str>) -> Result<(), BassError> {
+    // BASS makes its own copy of the string, so it only has to outlive the call.
+    let value = match value {
+        Some(value) => Some(CString::new(value).map_err(|_| BassError::IllegalParameter)?),
+        None => None,
+    };
+
+    let value_raw = match &value {
+        Some(value) => value.as_ptr() as *mut c_void,
+        None => ptr::null_mut(),
+    };
+
+    if BASS_SetConfigPtr(option, value_raw) == 0 {
+        return Err(BassError::last());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,414 @@
+use crate::sample::{i16_to_f32, u8_to_f32};
+use crate::{
+    Bass, BassError, Position, PositionFlags, PositionMode, SampleFormat, Stream, StreamBuilder,
+};
+
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+use std::mem;
+use std::time::Duration;
+
+use bass_sys::*;
+
+const FRAMES_BUFFER_LENGTH: usize = 4096;
+
+/// A decoding channel, it can't be played but its PCM data can be read.
+pub struct DecodeStream {
+    stream: Stream,
+    format: SampleFormat,
+    channels: u32,
+    frequency: u32,
+    pending: Pending,
+}
+
+impl TryFrom<Stream> for DecodeStream {
+    type Error = BassError;
+
+    fn try_from(stream: Stream) -> Result<DecodeStream, BassError> {
+        let info = stream.info()?;
+
+        if !info.is_decoding() {
+            return Err(BassError::NotDecodingChannel);
+        }
+
+        Ok(DecodeStream {
+            stream,
+            format: info.get_sample_format(),
+            channels: info.get_channels(),
+            frequency: info.get_frequency(),
+            pending: Pending::new(info.get_sample_format()),
+        })
+    }
+}
+
+impl DecodeStream {
+    pub fn create_from_file(
+        bass: &Bass,
+        file_name: String,
+        format: SampleFormat,
+    ) -> Result<DecodeStream, BassError> {
+        let stream = StreamBuilder::new(bass)
+            .sample_format(format)
+            .decode_only()
+            .create_from_file(file_name)?;
+
+        DecodeStream::try_from(stream)
+    }
+
+    pub fn get_sample_format(&self) -> SampleFormat {
+        self.format
+    }
+
+    pub fn get_channels(&self) -> u32 {
+        self.channels
+    }
+
+    pub fn get_frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// Decodes interleaved samples, converted to floating-point if needed, and returns how many were read, `0` once the stream has ended.
+    ///
+    /// A frame partly read with `Read` is finished first, by itself.
+    pub fn read_f32(&mut self, buffer: &mut [f32]) -> Result<usize, BassError> {
+        if !self.pending.is_empty() {
+            return self.pending.read_f32(buffer);
+        }
+
+        let read = self.read_raw(
+            buffer.as_mut_ptr() as *mut c_void,
+            mem::size_of_val(buffer),
+            BASS_DATA_FLOAT,
+        )?;
+
+        Ok(read / mem::size_of::<f32>())
+    }
+
+    /// Decodes interleaved samples and returns how many were read, `0` once the stream has ended.
+    ///
+    /// The stream has to be a 16-bit one. A frame partly read with `Read` is finished first, by itself.
+    pub fn read_i16(&mut self, buffer: &mut [i16]) -> Result<usize, BassError> {
+        if self.format != SampleFormat::Int16 {
+            return Err(BassError::InvalidSampleFormat);
+        }
+
+        if !self.pending.is_empty() {
+            return self.pending.read_i16(buffer);
+        }
+
+        let read = self.read_raw(
+            buffer.as_mut_ptr() as *mut c_void,
+            mem::size_of_val(buffer),
+            0,
+        )?;
+
+        Ok(read / mem::size_of::<i16>())
+    }
+
+    /// Returns an iterator over the interleaved frames, converted to floating-point.
+    pub fn frames(&mut self) -> Frames<'_> {
+        let channels = self.channels.max(1) as usize;
+
+        Frames {
+            stream: self,
+            buffer: vec![0.0; FRAMES_BUFFER_LENGTH * channels],
+            length: 0,
+            index: 0,
+        }
+    }
+
+    pub fn seek(&mut self, position: Position) -> Result<(), BassError> {
+        self.seek_with_flags(position, PositionFlags::default())
+    }
+
+    pub fn seek_with_flags(
+        &mut self,
+        position: Position,
+        flags: PositionFlags,
+    ) -> Result<(), BassError> {
+        self.stream.seek_with_flags(position, flags)?;
+        self.pending.clear();
+
+        Ok(())
+    }
+
+    pub fn position(&self, mode: PositionMode) -> Result<u64, BassError> {
+        self.stream.position(mode, PositionFlags::default())
+    }
+
+    pub fn length(&self, mode: PositionMode) -> Result<u64, BassError> {
+        self.stream.length(mode)
+    }
+
+    pub fn duration(&self) -> Result<Duration, BassError> {
+        self.stream.duration()
+    }
+
+    pub fn get_raw_handle(&self) -> &HSTREAM {
+        self.stream.get_raw_handle()
+    }
+
+    pub(crate) fn read_raw(
+        &mut self,
+        buffer: *mut c_void,
+        length: usize,
+        flags: DWORD,
+    ) -> Result<usize, BassError> {
+        // A length of 0 would only query the amount of data available.
+        if length == 0 {
+            return Ok(0);
+        }
+
+        // The flags live in the upper bits of the length, so it has to be kept below them.
+        let length = length.min(BASS_DATA_NOREMOVE as usize - 1);
+
+        let read = BASS_ChannelGetData(
+            *self.stream.get_raw_handle(),
+            buffer,
+            length as DWORD | flags,
+        );
+
+        if read == DWORD::MAX {
+            return match BassError::last() {
+                BassError::Ended => Ok(0),
+                error => Err(error),
+            };
+        }
+
+        Ok(read as usize)
+    }
+}
+
+/// Reads the raw interleaved PCM data in the sample format of the stream.
+impl Read for DecodeStream {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
+        if !self.pending.is_empty() {
+            return Ok(self.pending.read_bytes(buffer));
+        }
+
+        let frame_size = self.channels.max(1) as usize * self.format.get_size();
+
+        if buffer.len() >= frame_size {
+            return self
+                .read_raw(buffer.as_mut_ptr() as *mut c_void, buffer.len(), 0)
+                .map_err(io::Error::from);
+        }
+
+        // BASS only returns whole samples, so a buffer smaller than a frame is filled from a full one.
+        let mut frame = vec![0u8; frame_size];
+
+        let read = self.read_raw(frame.as_mut_ptr() as *mut c_void, frame.len(), 0)?;
+        let length = buffer.len().min(read);
+
+        buffer[..length].copy_from_slice(&frame[..length]);
+        self.pending.push(&frame[length..read]);
+
+        Ok(length)
+    }
+}
+
+/// Seeks in bytes of the raw PCM data returned by `Read`.
+impl Seek for DecodeStream {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => {
+                let current = self.stream_position()?;
+
+                current.checked_add_signed(offset)
+            }
+            SeekFrom::End(offset) => {
+                let length = self.length(PositionMode::Bytes)?;
+
+                length.checked_add_signed(offset)
+            }
+        };
+
+        let position = position.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })?;
+
+        DecodeStream::seek(self, Position::Bytes(position))?;
+
+        Ok(position)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        let position = self.position(PositionMode::Bytes)?;
+
+        Ok(position - self.pending.len() as u64)
+    }
+}
+
+/// Iterator over the interleaved frames of a `DecodeStream`, see `DecodeStream::frames`.
+pub struct Frames<'a> {
+    stream: &'a mut DecodeStream,
+    buffer: Vec<f32>,
+    length: usize,
+    index: usize,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<Vec<f32>, BassError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let channels = self.stream.channels.max(1) as usize;
+
+        if self.index >= self.length {
+            match self.stream.read_f32(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(read) => {
+                    self.length = read;
+                    self.index = 0;
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+
+        // The rest of a frame partly read with `Read` comes alone, so the next ones are whole.
+        let end = (self.index + channels).min(self.length);
+        let frame = self.buffer[self.index..end].to_vec();
+
+        self.index = end;
+
+        Some(Ok(frame))
+    }
+}
+
+/// The rest of a frame partly returned by `Read`, in the sample format of the stream.
+struct Pending {
+    format: SampleFormat,
+    data: Vec<u8>,
+}
+
+impl Pending {
+    fn new(format: SampleFormat) -> Pending {
+        Pending {
+            format,
+            data: Vec::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+
+    fn read_bytes(&mut self, buffer: &mut [u8]) -> usize {
+        let length = buffer.len().min(self.data.len());
+
+        buffer[..length].copy_from_slice(&self.data[..length]);
+        self.data.drain(..length);
+
+        length
+    }
+
+    fn read_f32(&mut self, buffer: &mut [f32]) -> Result<usize, BassError> {
+        let format = self.format;
+
+        self.read_samples(buffer, |bytes| match format {
+            SampleFormat::Float => f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            SampleFormat::Int16 => i16_to_f32(i16::from_ne_bytes([bytes[0], bytes[1]])),
+            SampleFormat::Int8 => u8_to_f32(bytes[0]),
+        })
+    }
+
+    fn read_i16(&mut self, buffer: &mut [i16]) -> Result<usize, BassError> {
+        self.read_samples(buffer, |bytes| i16::from_ne_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Converts the pending samples, it fails if `Read` stopped in the middle of one.
+    fn read_samples<T, F>(&mut self, buffer: &mut [T], convert: F) -> Result<usize, BassError>
+    where
+        F: Fn(&[u8]) -> T,
+    {
+        let size = self.format.get_size();
+
+        if !self.data.len().is_multiple_of(size) {
+            return Err(BassError::InvalidPosition);
+        }
+
+        let count = buffer.len().min(self.data.len() / size);
+
+        for (sample, bytes) in buffer.iter_mut().zip(self.data.chunks_exact(size)) {
+            *sample = convert(bytes);
+        }
+
+        self.data.drain(..count * size);
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_i16(samples: &[i16]) -> Pending {
+        let mut pending = Pending::new(SampleFormat::Int16);
+
+        for sample in samples {
+            pending.push(&sample.to_ne_bytes());
+        }
+
+        pending
+    }
+
+    #[test]
+    fn typed_reads_continue_after_bytes() {
+        let mut pending = pending_i16(&[1, 2, 3]);
+        let mut bytes = [0u8; 2];
+        let mut samples = [0i16; 4];
+
+        assert_eq!(pending.read_bytes(&mut bytes), 2);
+        assert_eq!(i16::from_ne_bytes(bytes), 1);
+        assert_eq!(pending.read_i16(&mut samples).unwrap(), 2);
+        assert_eq!(samples[..2], [2, 3]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn pending_samples_convert_to_f32() {
+        let mut pending = pending_i16(&[16384, -32768]);
+        let mut samples = [0f32; 1];
+
+        assert_eq!(pending.read_f32(&mut samples).unwrap(), 1);
+        assert_eq!(samples, [0.5]);
+        assert_eq!(pending.read_f32(&mut samples).unwrap(), 1);
+        assert_eq!(samples, [-1.0]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn split_sample_is_rejected() {
+        let mut pending = pending_i16(&[1, 2]);
+        let mut byte = [0u8; 1];
+        let mut samples = [0i16; 2];
+
+        assert_eq!(pending.read_bytes(&mut byte), 1);
+        assert!(matches!(
+            pending.read_i16(&mut samples),
+            Err(BassError::InvalidPosition)
+        ));
+        assert_eq!(pending.len(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::BassError;
+
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::ptr;
+
+use bass_sys::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceType {
+    Unknown,
+    Network,
+    Speakers,
+    Line,
+    Headphones,
+    Microphone,
+    Headset,
+    Handset,
+    Digital,
+    Spdif,
+    Hdmi,
+    DisplayPort,
+}
+
+impl DeviceType {
+    fn from_flags(flags: DWORD) -> DeviceType {
+        match flags & BASS_DEVICE_TYPE_MASK {
+            BASS_DEVICE_TYPE_NETWORK => DeviceType::Network,
+            BASS_DEVICE_TYPE_SPEAKERS => DeviceType::Speakers,
+            BASS_DEVICE_TYPE_LINE => DeviceType::Line,
+            BASS_DEVICE_TYPE_HEADPHONES => DeviceType::Headphones,
+            BASS_DEVICE_TYPE_MICROPHONE => DeviceType::Microphone,
+            BASS_DEVICE_TYPE_HEADSET => DeviceType::Headset,
+            BASS_DEVICE_TYPE_HANDSET => DeviceType::Handset,
+            BASS_DEVICE_TYPE_DIGITAL => DeviceType::Digital,
+            BASS_DEVICE_TYPE_SPDIF => DeviceType::Spdif,
+            BASS_DEVICE_TYPE_HDMI => DeviceType::Hdmi,
+            BASS_DEVICE_TYPE_DISPLAYPORT => DeviceType::DisplayPort,
+            _ => DeviceType::Unknown,
+        }
+    }
+}
+
+/// An output device, as reported by `BASS_GetDeviceInfo`.
+///
+/// Device `0` is the "no sound" device, which is always available.
+#[derive(Debug, Clone)]
+pub struct Device {
+    number: u32,
+    name: String,
+    driver: Option<String>,
+    flags: DWORD,
+}
+
+impl Device {
+    pub fn get(number: u32) -> Result<Device, BassError> {
+        let mut info = BassDeviceInfo::new(ptr::null(), ptr::null(), 0);
+
+        if BASS_GetDeviceInfo(number, &mut info as *mut BassDeviceInfo) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Device {
+            number,
+            name: string_from_raw(info.name).unwrap_or_default(),
+            driver: string_from_raw(info.driver),
+            flags: info.flags,
+        })
+    }
+
+    /// Returns the device that is current for the calling thread.
+    pub fn current() -> Result<Device, BassError> {
+        let number = BASS_GetDevice();
+
+        if number == DWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Device::get(number)
+    }
+
+    pub fn all() -> Devices {
+        Devices { next: 0 }
+    }
+
+    /// Makes this device the current one for the calling thread, it has to be initialized first.
+    pub fn make_current(&self) -> Result<(), BassError> {
+        if BASS_SetDevice(self.number) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(())
+    }
+
+    pub fn get_number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_driver(&self) -> Option<&str> {
+        self.driver.as_deref()
+    }
+
+    pub fn get_type(&self) -> DeviceType {
+        DeviceType::from_flags(self.flags)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.flags & BASS_DEVICE_ENABLED != 0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.flags & BASS_DEVICE_DEFAULT != 0
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.flags & BASS_DEVICE_INIT != 0
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        self.flags & BASS_DEVICE_LOOPBACK != 0
+    }
+}
+
+/// Iterator over all output devices, starting with the "no sound" device.
+pub struct Devices {
+    next: u32,
+}
+
+impl Iterator for Devices {
+    type Item = Device;
+
+    fn next(&mut self) -> Option<Device> {
+        let device = Device::get(self.next).ok()?;
+
+        self.next += 1;
+
+        Some(device)
+    }
+}
+
+fn string_from_raw(raw: *const c_void) -> Option<String> {
+    if raw.is_null() {
+        return None;
+    }
+
+    let raw = unsafe { CStr::from_ptr(raw as *const c_char) };
+
+    Some(raw.to_string_lossy().into_owned())
+}
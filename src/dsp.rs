@@ -0,0 +1,112 @@
+use crate::user_data::UserData;
+use crate::{BassError, Config, Sample, SampleFormat};
+
+use std::ffi::c_void;
+use std::mem;
+use std::os::raw::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use bass_sys::*;
+
+type DspCallback = Box<dyn FnMut(*mut c_void, DWORD) + Send>;
+
+/// A DSP function set on a channel, it's removed when dropped.
+pub struct DspHandle {
+    channel: DWORD,
+    handle: HDSP,
+    _callback: UserData<DspCallback>,
+}
+
+impl Drop for DspHandle {
+    fn drop(&mut self) {
+        BASS_ChannelRemoveDSP(self.channel, self.handle);
+    }
+}
+
+impl DspHandle {
+    /// The callback is skipped while `Config::set_float_dsp` makes the format differ from `T`, and for good once it panics.
+    pub(crate) fn new<T, F>(
+        channel: DWORD,
+        native_format: SampleFormat,
+        priority: i32,
+        mut callback: F,
+    ) -> Result<DspHandle, BassError>
+    where
+        T: Sample,
+        F: FnMut(&mut [T]) + Send + 'static,
+    {
+        if dsp_format(native_format)? != T::FORMAT {
+            return Err(BassError::InvalidSampleFormat);
+        }
+
+        let mut panicked = false;
+
+        let callback: DspCallback = Box::new(move |buffer, length| {
+            if panicked || buffer.is_null() {
+                return;
+            }
+
+            if dsp_format(native_format).ok() != Some(T::FORMAT) {
+                return;
+            }
+
+            let samples = unsafe {
+                slice::from_raw_parts_mut(buffer as *mut T, length as usize / mem::size_of::<T>())
+            };
+
+            // Unwinding into BASS is undefined behaviour, a panicking callback is disabled instead.
+            if panic::catch_unwind(AssertUnwindSafe(|| callback(samples))).is_err() {
+                panicked = true;
+            }
+        });
+
+        let callback = UserData::new(callback);
+
+        let handle = BASS_ChannelSetDSP(
+            channel,
+            dsp_proc as *mut DSPPROC,
+            callback.as_raw(),
+            priority as c_int,
+        );
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(DspHandle {
+            channel,
+            handle,
+            _callback: callback,
+        })
+    }
+
+    /// Changes the position of the DSP in the chain, higher priorities are applied first.
+    pub fn set_priority(&self, priority: i32) -> Result<(), BassError> {
+        if BASS_FXSetPriority(self.handle, priority as c_int) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn dsp_format(native_format: SampleFormat) -> Result<SampleFormat, BassError> {
+    if native_format == SampleFormat::Float || Config::get_float_dsp()? {
+        Ok(SampleFormat::Float)
+    } else {
+        Ok(native_format)
+    }
+}
+
+extern "system" fn dsp_proc(
+    _handle: HDSP,
+    _channel: DWORD,
+    buffer: *mut c_void,
+    length: DWORD,
+    user: *mut c_void,
+) {
+    let callback = unsafe { &mut *(user as *mut DspCallback) };
+
+    callback(buffer, length);
+}
@@ -1,3 +1,6 @@
+use bass_sys::*;
+use std::io;
+use std::os::raw::c_int;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -32,4 +35,232 @@ pub enum BassError {
     UnstreamableFile,
     #[error("The server didn't respond to the request within the timeout period.")]
     TimeOut,
+    #[error("The device is already initialized or the stream is already paused.")]
+    AlreadyInitialized,
+    #[error("The device is invalid or doesn't exist.")]
+    InvalidDevice,
+    #[error("The device hasn't been initialized.")]
+    DeviceIsNotInitialized,
+    #[error("There is no available device driver.")]
+    NoDriverAvailable,
+    #[error("The device is busy.")]
+    DeviceIsBusy,
+    #[error("The handle is invalid.")]
+    InvalidHandle,
+    #[error("An illegal parameter was specified.")]
+    IllegalParameter,
+    #[error("An illegal type was specified.")]
+    IllegalType,
+    #[error("The sample buffer was lost.")]
+    BufferLost,
+    #[error("The position is invalid.")]
+    InvalidPosition,
+    #[error("The device needs to be reinitialized.")]
+    DeviceNeedsReinitialization,
+    #[error("There is no free channel.")]
+    NoFreeChannel,
+    #[error("EAX support is not available.")]
+    EaxSupportNotAvailable,
+    #[error("The sample rate is invalid.")]
+    InvalidSampleRate,
+    #[error("The stream is not a file stream.")]
+    StreamIsNotFileStream,
+    #[error("There are no hardware voices available.")]
+    NoHardwareVoicesAvailable,
+    #[error("The music has no sequence data.")]
+    MusicHasNoSequenceData,
+    #[error("The file couldn't be created.")]
+    FileCouldNotBeCreated,
+    #[error("Effects are not available.")]
+    EffectsNotAvailable,
+    #[error("The requested data or action is not available.")]
+    NotAvailable,
+    #[error("A sufficient DirectX version is not installed.")]
+    DirectXNotAvailable,
+    #[error("The speaker is not available.")]
+    SpeakerNotAvailable,
+    #[error("The BASS version is invalid.")]
+    InvalidVersion,
+    #[error("The stream has ended.")]
+    Ended,
+    #[error("The stream is not a decoding channel.")]
+    NotDecodingChannel,
+    #[error("The value is out of the allowed range.")]
+    ValueOutOfRange,
+    #[error("An unknown error occurred.")]
+    Unknown,
+    #[error("An unrecognised error occurred, error code: {0}")]
+    Other(c_int),
+}
+
+impl BassError {
+    pub fn from_code(code: c_int) -> BassError {
+        match code {
+            BASS_ERROR_MEM => BassError::InsufficientMemory,
+            BASS_ERROR_FILEOPEN => BassError::FileCouldNotBeOpened,
+            BASS_ERROR_DRIVER => BassError::NoDriverAvailable,
+            BASS_ERROR_BUFLOST => BassError::BufferLost,
+            BASS_ERROR_HANDLE => BassError::InvalidHandle,
+            BASS_ERROR_FORMAT => BassError::InvalidSampleFormat,
+            BASS_ERROR_POSITION => BassError::InvalidPosition,
+            BASS_ERROR_INIT => BassError::DeviceIsNotInitialized,
+            BASS_ERROR_START => BassError::OutputIsPausedOrStopped,
+            BASS_ERROR_SSL => BassError::SslSupportNotAvailable,
+            BASS_ERROR_REINIT => BassError::DeviceNeedsReinitialization,
+            BASS_ERROR_ALREADY => BassError::AlreadyInitialized,
+            BASS_ERROR_NOTAUDIO => BassError::InvalidFileContent,
+            BASS_ERROR_NOCHAN => BassError::NoFreeChannel,
+            BASS_ERROR_ILLTYPE => BassError::IllegalType,
+            BASS_ERROR_ILLPARAM => BassError::IllegalParameter,
+            BASS_ERROR_NO3D => BassError::CouldNotInitialize3DSupport,
+            BASS_ERROR_NOEAX => BassError::EaxSupportNotAvailable,
+            BASS_ERROR_DEVICE => BassError::InvalidDevice,
+            BASS_ERROR_NOPLAY => BassError::StreamIsNotPlaying,
+            BASS_ERROR_FREQ => BassError::InvalidSampleRate,
+            BASS_ERROR_NOTFILE => BassError::StreamIsNotFileStream,
+            BASS_ERROR_NOHW => BassError::NoHardwareVoicesAvailable,
+            BASS_ERROR_EMPTY => BassError::MusicHasNoSequenceData,
+            BASS_ERROR_NONET => BassError::NoInternetConnection,
+            BASS_ERROR_CREATE => BassError::FileCouldNotBeCreated,
+            BASS_ERROR_NOFX => BassError::EffectsNotAvailable,
+            BASS_ERROR_NOTAVAIL => BassError::NotAvailable,
+            BASS_ERROR_DECODE => BassError::StreamIsNotPlayable,
+            BASS_ERROR_DX => BassError::DirectXNotAvailable,
+            BASS_ERROR_TIMEOUT => BassError::TimeOut,
+            BASS_ERROR_FILEFORM => BassError::InvalidFileFormat,
+            BASS_ERROR_SPEAKER => BassError::SpeakerNotAvailable,
+            BASS_ERROR_VERSION => BassError::InvalidVersion,
+            BASS_ERROR_CODEC => BassError::InvalidCodec,
+            BASS_ERROR_ENDED => BassError::Ended,
+            BASS_ERROR_BUSY => BassError::DeviceIsBusy,
+            BASS_ERROR_UNSTREAMABLE => BassError::UnstreamableFile,
+            BASS_ERROR_PROTOCOL => BassError::InvalidProtocol,
+            BASS_ERROR_UNKNOWN => BassError::Unknown,
+            _ => BassError::Other(code),
+        }
+    }
+
+    /// Returns the error of the last failed BASS call on the calling thread.
+    pub(crate) fn last() -> BassError {
+        BassError::from_code(BASS_ErrorGetCode())
+    }
+
+    /// Returns the `BASS_ERROR_*` code this error corresponds to.
+    pub fn code(&self) -> c_int {
+        match self {
+            BassError::OutputIsPausedOrStopped => BASS_ERROR_START,
+            BassError::StreamIsNotPlayable | BassError::NotDecodingChannel => BASS_ERROR_DECODE,
+            BassError::StreamIsNotPlaying => BASS_ERROR_NOPLAY,
+            BassError::FileCouldNotBeOpened => BASS_ERROR_FILEOPEN,
+            BassError::InvalidFileFormat => BASS_ERROR_FILEFORM,
+            BassError::InvalidFileContent => BASS_ERROR_NOTAUDIO,
+            BassError::InvalidCodec => BASS_ERROR_CODEC,
+            BassError::InvalidSampleFormat => BASS_ERROR_FORMAT,
+            BassError::InsufficientMemory => BASS_ERROR_MEM,
+            BassError::CouldNotInitialize3DSupport => BASS_ERROR_NO3D,
+            BassError::NoInternetConnection => BASS_ERROR_NONET,
+            BassError::InvalidProtocol => BASS_ERROR_PROTOCOL,
+            BassError::SslSupportNotAvailable => BASS_ERROR_SSL,
+            BassError::UnstreamableFile => BASS_ERROR_UNSTREAMABLE,
+            BassError::TimeOut => BASS_ERROR_TIMEOUT,
+            BassError::AlreadyInitialized => BASS_ERROR_ALREADY,
+            BassError::InvalidDevice => BASS_ERROR_DEVICE,
+            BassError::DeviceIsNotInitialized => BASS_ERROR_INIT,
+            BassError::NoDriverAvailable => BASS_ERROR_DRIVER,
+            BassError::DeviceIsBusy => BASS_ERROR_BUSY,
+            BassError::InvalidHandle => BASS_ERROR_HANDLE,
+            BassError::IllegalParameter | BassError::ValueOutOfRange => BASS_ERROR_ILLPARAM,
+            BassError::IllegalType => BASS_ERROR_ILLTYPE,
+            BassError::BufferLost => BASS_ERROR_BUFLOST,
+            BassError::InvalidPosition => BASS_ERROR_POSITION,
+            BassError::DeviceNeedsReinitialization => BASS_ERROR_REINIT,
+            BassError::NoFreeChannel => BASS_ERROR_NOCHAN,
+            BassError::EaxSupportNotAvailable => BASS_ERROR_NOEAX,
+            BassError::InvalidSampleRate => BASS_ERROR_FREQ,
+            BassError::StreamIsNotFileStream => BASS_ERROR_NOTFILE,
+            BassError::NoHardwareVoicesAvailable => BASS_ERROR_NOHW,
+            BassError::MusicHasNoSequenceData => BASS_ERROR_EMPTY,
+            BassError::FileCouldNotBeCreated => BASS_ERROR_CREATE,
+            BassError::EffectsNotAvailable => BASS_ERROR_NOFX,
+            BassError::NotAvailable => BASS_ERROR_NOTAVAIL,
+            BassError::DirectXNotAvailable => BASS_ERROR_DX,
+            BassError::SpeakerNotAvailable => BASS_ERROR_SPEAKER,
+            BassError::InvalidVersion => BASS_ERROR_VERSION,
+            BassError::Ended => BASS_ERROR_ENDED,
+            BassError::Unknown => BASS_ERROR_UNKNOWN,
+            BassError::Other(code) => *code,
+        }
+    }
+}
+
+impl From<BassError> for io::Error {
+    fn from(error: BassError) -> io::Error {
+        io::Error::other(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        let codes = [
+            BASS_ERROR_MEM,
+            BASS_ERROR_FILEOPEN,
+            BASS_ERROR_DRIVER,
+            BASS_ERROR_BUFLOST,
+            BASS_ERROR_HANDLE,
+            BASS_ERROR_FORMAT,
+            BASS_ERROR_POSITION,
+            BASS_ERROR_INIT,
+            BASS_ERROR_START,
+            BASS_ERROR_SSL,
+            BASS_ERROR_REINIT,
+            BASS_ERROR_ALREADY,
+            BASS_ERROR_NOTAUDIO,
+            BASS_ERROR_NOCHAN,
+            BASS_ERROR_ILLTYPE,
+            BASS_ERROR_ILLPARAM,
+            BASS_ERROR_NO3D,
+            BASS_ERROR_NOEAX,
+            BASS_ERROR_DEVICE,
+            BASS_ERROR_NOPLAY,
+            BASS_ERROR_FREQ,
+            BASS_ERROR_NOTFILE,
+            BASS_ERROR_NOHW,
+            BASS_ERROR_EMPTY,
+            BASS_ERROR_NONET,
+            BASS_ERROR_CREATE,
+            BASS_ERROR_NOFX,
+            BASS_ERROR_NOTAVAIL,
+            BASS_ERROR_DECODE,
+            BASS_ERROR_DX,
+            BASS_ERROR_TIMEOUT,
+            BASS_ERROR_FILEFORM,
+            BASS_ERROR_SPEAKER,
+            BASS_ERROR_VERSION,
+            BASS_ERROR_CODEC,
+            BASS_ERROR_ENDED,
+            BASS_ERROR_BUSY,
+            BASS_ERROR_UNSTREAMABLE,
+            BASS_ERROR_PROTOCOL,
+            BASS_ERROR_UNKNOWN,
+        ];
+
+        for code in codes {
+            let error = BassError::from_code(code);
+
+            assert!(!matches!(error, BassError::Other(_)), "code {}", code);
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[test]
+    fn unknown_code_is_other() {
+        let error = BassError::from_code(12345);
+
+        assert!(matches!(error, BassError::Other(12345)));
+        assert_eq!(error.code(), 12345);
+    }
 }
@@ -0,0 +1,81 @@
+use crate::{BassError, SyncEvent, SyncHandle, SyncKind};
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use bass_sys::*;
+
+/// A playback event, received from `Stream::events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackEvent {
+    Ended,
+    /// A position added with `Stream::add_event_position` was reached, in bytes.
+    PositionReached(u64),
+    Stalled,
+    Resumed,
+    MetadataChanged,
+    Freed,
+    DeviceFailed,
+}
+
+/// The syncs feeding the receiver returned by `Stream::events`.
+pub(crate) struct EventSyncs {
+    channel: DWORD,
+    sender: Sender<PlaybackEvent>,
+    syncs: Vec<SyncHandle>,
+}
+
+impl EventSyncs {
+    pub(crate) fn new(channel: DWORD) -> Result<(EventSyncs, Receiver<PlaybackEvent>), BassError> {
+        let (sender, receiver) = mpsc::channel();
+
+        let mut events = EventSyncs {
+            channel,
+            sender,
+            syncs: Vec::new(),
+        };
+
+        for kind in [
+            SyncKind::End,
+            SyncKind::Stall,
+            SyncKind::Meta,
+            SyncKind::OggChange,
+            SyncKind::Free,
+            SyncKind::DeviceFail,
+        ] {
+            events.add(kind)?;
+        }
+
+        Ok((events, receiver))
+    }
+
+    pub(crate) fn add(&mut self, kind: SyncKind) -> Result<(), BassError> {
+        let sender = self.sender.clone();
+
+        let sync = SyncHandle::new(
+            self.channel,
+            kind.raw(),
+            kind.parameter(),
+            Box::new(move |data| {
+                let event = match kind.event(data) {
+                    SyncEvent::Ended => PlaybackEvent::Ended,
+                    SyncEvent::PositionReached => PlaybackEvent::PositionReached(kind.parameter()),
+                    SyncEvent::Stalled => PlaybackEvent::Stalled,
+                    SyncEvent::Resumed => PlaybackEvent::Resumed,
+                    SyncEvent::MetadataChanged | SyncEvent::OggBitstreamChanged => {
+                        PlaybackEvent::MetadataChanged
+                    }
+                    SyncEvent::Freed => PlaybackEvent::Freed,
+                    SyncEvent::DeviceFailed => PlaybackEvent::DeviceFailed,
+                    _ => return,
+                };
+
+                // The receiver may have been dropped, nobody is listening then.
+                let _ = sender.send(event);
+            }),
+        )?;
+
+        self.syncs.push(sync);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{BassError, SyncHandle};
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use bass_sys::*;
+
+enum Outcome {
+    Triggered,
+    Freed,
+}
+
+#[derive(Default)]
+struct Shared {
+    outcome: Option<Outcome>,
+    waker: Option<Waker>,
+}
+
+enum State {
+    Waiting {
+        shared: Arc<Mutex<Shared>>,
+        _syncs: [SyncHandle; 2],
+    },
+    Done,
+    Failed(Option<BassError>),
+}
+
+/// A future resolving when a sync is triggered, the sync is removed when it's dropped.
+///
+/// It resolves with `BassError::InvalidHandle` if the channel is freed first. It doesn't depend on any executor, the waker is called from the BASS thread triggering the sync.
+pub struct SyncFuture {
+    state: State,
+}
+
+impl SyncFuture {
+    pub(crate) fn new(channel: DWORD, sync_type: DWORD, parameter: QWORD) -> SyncFuture {
+        SyncFuture::create(channel, sync_type | BASS_SYNC_ONETIME, parameter, |_| true)
+    }
+
+    /// Creates a future only resolved by the syncs passing `filter`.
+    ///
+    /// The sync isn't one-time then, as BASS would remove it after a rejected call.
+    pub(crate) fn with_filter<F>(
+        channel: DWORD,
+        sync_type: DWORD,
+        parameter: QWORD,
+        filter: F,
+    ) -> SyncFuture
+    where
+        F: Fn(DWORD) -> bool + Send + 'static,
+    {
+        SyncFuture::create(channel, sync_type, parameter, filter)
+    }
+
+    fn create<F>(channel: DWORD, sync_type: DWORD, parameter: QWORD, filter: F) -> SyncFuture
+    where
+        F: Fn(DWORD) -> bool + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+
+        let triggered = {
+            let shared = shared.clone();
+
+            SyncHandle::new(
+                channel,
+                sync_type,
+                parameter,
+                Box::new(move |data| {
+                    if filter(data) {
+                        complete(&shared, Outcome::Triggered);
+                    }
+                }),
+            )
+        };
+
+        let freed = {
+            let shared = shared.clone();
+
+            SyncHandle::new(
+                channel,
+                BASS_SYNC_FREE | BASS_SYNC_ONETIME,
+                0,
+                Box::new(move |_| complete(&shared, Outcome::Freed)),
+            )
+        };
+
+        let state = match (triggered, freed) {
+            (Ok(triggered), Ok(freed)) => State::Waiting {
+                shared,
+                _syncs: [triggered, freed],
+            },
+            (Err(error), _) | (_, Err(error)) => State::Failed(Some(error)),
+        };
+
+        SyncFuture { state }
+    }
+
+    /// Resolves the future right away, for when the awaited condition is already met.
+    pub(crate) fn resolve(&mut self) {
+        if let State::Waiting { .. } = self.state {
+            self.state = State::Done;
+        }
+    }
+}
+
+impl Future for SyncFuture {
+    type Output = Result<(), BassError>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.state {
+            State::Waiting { shared, .. } => {
+                let mut shared = lock(shared);
+
+                match shared.outcome {
+                    Some(Outcome::Triggered) => {}
+                    Some(Outcome::Freed) => {
+                        drop(shared);
+
+                        self.state = State::Done;
+
+                        return Poll::Ready(Err(BassError::InvalidHandle));
+                    }
+                    None => {
+                        match &shared.waker {
+                            Some(waker) if waker.will_wake(context.waker()) => {}
+                            _ => shared.waker = Some(context.waker().clone()),
+                        }
+
+                        return Poll::Pending;
+                    }
+                }
+            }
+            State::Done => {}
+            State::Failed(error) => {
+                let error = error.take().expect("SyncFuture polled after completion");
+
+                return Poll::Ready(Err(error));
+            }
+        }
+
+        self.state = State::Done;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Records the first outcome and wakes the task waiting for it.
+fn complete(shared: &Mutex<Shared>, outcome: Outcome) {
+    let waker = {
+        let mut shared = lock(shared);
+
+        if shared.outcome.is_some() {
+            return;
+        }
+
+        shared.outcome = Some(outcome);
+        shared.waker.take()
+    };
+
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> std::sync::MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|error| error.into_inner())
+}
@@ -1,5 +1,46 @@
+mod attribute;
+mod bass;
+mod channel_info;
+mod channel_state;
+mod config;
+mod decode_stream;
+mod device;
+mod dsp;
 mod error;
+mod events;
+#[cfg(feature = "async")]
+mod future;
+mod position;
+mod processors;
+mod push_stream;
+mod reader;
+mod sample;
 mod stream;
+mod stream_builder;
+mod stream_proc;
+mod sync;
+mod user_data;
+mod wav;
 
+pub use attribute::*;
+pub use bass::*;
+pub use channel_info::*;
+pub use channel_state::*;
+pub use config::*;
+pub use decode_stream::*;
+pub use device::*;
+pub use dsp::*;
 pub use error::*;
+pub use events::*;
+#[cfg(feature = "async")]
+pub use future::*;
+pub use position::*;
+pub use processors::*;
+pub use push_stream::*;
+pub use reader::*;
+pub use sample::*;
 pub use stream::*;
+pub use stream_builder::*;
+pub use stream_proc::*;
+pub use sync::*;
+pub use wav::*;
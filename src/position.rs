@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use bass_sys::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    Bytes(u64),
+    Time(Duration),
+    MusicOrder { order: u16, row: u16 },
+    OggBitstream(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionMode {
+    Bytes,
+    MusicOrder,
+    OggBitstream,
+}
+
+impl PositionMode {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            PositionMode::Bytes => BASS_POS_BYTE,
+            PositionMode::MusicOrder => BASS_POS_MUSIC_ORDER,
+            PositionMode::OggBitstream => BASS_POS_OGG,
+        }
+    }
+}
+
+/// Modifiers of position queries and seeks, see the `BASS_POS_*` flags.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PositionFlags {
+    /// Flushes the decoder and effect buffers when seeking.
+    pub flush: bool,
+    /// Seeks relative to the current position.
+    pub relative: bool,
+    /// Scans the file to reach the exact position instead of relying on seek tables.
+    pub scan: bool,
+    /// Uses the decoding position instead of the playback position.
+    pub decode: bool,
+}
+
+impl PositionFlags {
+    pub fn raw(&self) -> DWORD {
+        let mut flags = 0;
+
+        if self.flush {
+            flags |= BASS_POS_FLUSH;
+        }
+
+        if self.relative {
+            flags |= BASS_POS_RELATIVE;
+        }
+
+        if self.scan {
+            flags |= BASS_POS_SCAN;
+        }
+
+        if self.decode {
+            flags |= BASS_POS_DECODE;
+        }
+
+        flags
+    }
+}
@@ -0,0 +1,311 @@
+use crate::Processor;
+
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BiquadKind {
+    LowPass,
+    HighPass,
+    /// Boosts or cuts around the frequency, the gain is in dB.
+    Peaking {
+        gain: f32,
+    },
+    LowShelf {
+        gain: f32,
+    },
+    HighShelf {
+        gain: f32,
+    },
+}
+
+/// A second-order filter using the coefficients of the Audio EQ Cookbook.
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    kind: BiquadKind,
+    frequency: f32,
+    q: f32,
+    sample_rate: u32,
+    coefficients: [f64; 5],
+    state: Vec<[f64; 2]>,
+}
+
+impl Biquad {
+    pub fn new(kind: BiquadKind, frequency: f32, q: f32) -> Biquad {
+        let mut biquad = Biquad {
+            kind,
+            frequency,
+            q,
+            sample_rate: 44100,
+            coefficients: [1.0, 0.0, 0.0, 0.0, 0.0],
+            state: Vec::new(),
+        };
+
+        biquad.update();
+
+        biquad
+    }
+
+    pub fn get_kind(&self) -> BiquadKind {
+        self.kind
+    }
+
+    pub fn set_kind(&mut self, kind: BiquadKind) {
+        self.kind = kind;
+        self.update();
+    }
+
+    pub fn get_frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+        self.update();
+    }
+
+    pub fn get_q(&self) -> f32 {
+        self.q
+    }
+
+    pub fn set_q(&mut self, q: f32) {
+        self.q = q;
+        self.update();
+    }
+
+    fn update(&mut self) {
+        let nyquist = self.sample_rate as f64 / 2.0;
+        let frequency = (self.frequency as f64).clamp(1.0, nyquist * 0.999);
+        let q = (self.q as f64).max(0.01);
+
+        let w0 = 2.0 * PI * frequency / self.sample_rate as f64;
+        let cos = w0.cos();
+        let alpha = w0.sin() / (2.0 * q);
+
+        let [b0, b1, b2, a0, a1, a2] = match self.kind {
+            BiquadKind::LowPass => [
+                (1.0 - cos) / 2.0,
+                1.0 - cos,
+                (1.0 - cos) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos,
+                1.0 - alpha,
+            ],
+            BiquadKind::HighPass => [
+                (1.0 + cos) / 2.0,
+                -(1.0 + cos),
+                (1.0 + cos) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos,
+                1.0 - alpha,
+            ],
+            BiquadKind::Peaking { gain } => {
+                let a = 10f64.powf(gain as f64 / 40.0);
+
+                [
+                    1.0 + alpha * a,
+                    -2.0 * cos,
+                    1.0 - alpha * a,
+                    1.0 + alpha / a,
+                    -2.0 * cos,
+                    1.0 - alpha / a,
+                ]
+            }
+            BiquadKind::LowShelf { gain } => {
+                let a = 10f64.powf(gain as f64 / 40.0);
+                let beta = 2.0 * a.sqrt() * alpha;
+
+                [
+                    a * ((a + 1.0) - (a - 1.0) * cos + beta),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+                    a * ((a + 1.0) - (a - 1.0) * cos - beta),
+                    (a + 1.0) + (a - 1.0) * cos + beta,
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+                    (a + 1.0) + (a - 1.0) * cos - beta,
+                ]
+            }
+            BiquadKind::HighShelf { gain } => {
+                let a = 10f64.powf(gain as f64 / 40.0);
+                let beta = 2.0 * a.sqrt() * alpha;
+
+                [
+                    a * ((a + 1.0) + (a - 1.0) * cos + beta),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+                    a * ((a + 1.0) + (a - 1.0) * cos - beta),
+                    (a + 1.0) - (a - 1.0) * cos + beta,
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos),
+                    (a + 1.0) - (a - 1.0) * cos - beta,
+                ]
+            }
+        };
+
+        self.coefficients = [b0 / a0, b1 / a0, b2 / a0, a1 / a0, a2 / a0];
+    }
+}
+
+impl Processor for Biquad {
+    fn prepare(&mut self, sample_rate: u32, channels: usize) {
+        self.sample_rate = sample_rate.max(1);
+        self.state = vec![[0.0; 2]; channels];
+        self.update();
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        let channels = self.state.len().max(1);
+        let [b0, b1, b2, a1, a2] = self.coefficients;
+
+        for frame in samples.chunks_mut(channels) {
+            for (sample, state) in frame.iter_mut().zip(self.state.iter_mut()) {
+                let input = *sample as f64;
+                let output = b0 * input + state[0];
+
+                state[0] = b1 * input - a1 * output + state[1];
+                state[1] = b2 * input - a2 * output;
+
+                *sample = output as f32;
+            }
+        }
+    }
+}
+
+/// A chain of biquad bands, applied in order.
+#[derive(Debug, Clone, Default)]
+pub struct Equalizer {
+    bands: Vec<Biquad>,
+    format: Option<(u32, usize)>,
+}
+
+impl Equalizer {
+    pub fn new() -> Equalizer {
+        Equalizer::default()
+    }
+
+    /// Adds a band and returns its index.
+    pub fn add_band(&mut self, mut band: Biquad) -> usize {
+        if let Some((sample_rate, channels)) = self.format {
+            band.prepare(sample_rate, channels);
+        }
+
+        self.bands.push(band);
+        self.bands.len() - 1
+    }
+
+    pub fn remove_band(&mut self, index: usize) -> Option<Biquad> {
+        if index < self.bands.len() {
+            Some(self.bands.remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_band(&self, index: usize) -> Option<&Biquad> {
+        self.bands.get(index)
+    }
+
+    pub fn get_band_mut(&mut self, index: usize) -> Option<&mut Biquad> {
+        self.bands.get_mut(index)
+    }
+
+    pub fn get_band_count(&self) -> usize {
+        self.bands.len()
+    }
+}
+
+impl Processor for Equalizer {
+    fn prepare(&mut self, sample_rate: u32, channels: usize) {
+        self.format = Some((sample_rate, channels));
+
+        for band in &mut self.bands {
+            band.prepare(sample_rate, channels);
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for band in &mut self.bands {
+            band.process(samples);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples
+            .iter()
+            .fold(0f32, |peak, sample| peak.max(sample.abs()))
+    }
+
+    #[test]
+    fn low_pass_removes_nyquist() {
+        let mut biquad = Biquad::new(BiquadKind::LowPass, 1000.0, 0.707);
+
+        biquad.prepare(44100, 1);
+
+        let mut samples: Vec<f32> = (0..4410)
+            .map(|index| if index % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+
+        biquad.process(&mut samples);
+
+        assert!(peak(&samples[2000..]) < 1e-3);
+    }
+
+    #[test]
+    fn low_pass_keeps_dc() {
+        let mut biquad = Biquad::new(BiquadKind::LowPass, 1000.0, 0.707);
+
+        biquad.prepare(44100, 2);
+
+        let mut samples = vec![0.5; 2 * 4410];
+
+        biquad.process(&mut samples);
+
+        assert!(samples[samples.len() - 2..]
+            .iter()
+            .all(|sample| (sample - 0.5).abs() < 1e-4));
+    }
+
+    #[test]
+    fn high_pass_removes_dc() {
+        let mut biquad = Biquad::new(BiquadKind::HighPass, 100.0, 0.707);
+
+        biquad.prepare(44100, 1);
+
+        let mut samples = vec![1.0; 44100];
+
+        biquad.process(&mut samples);
+
+        assert!(samples[samples.len() - 1].abs() < 1e-4);
+    }
+
+    #[test]
+    fn peaking_boosts_its_frequency() {
+        let mut biquad = Biquad::new(BiquadKind::Peaking { gain: 6.0 }, 1000.0, 1.0);
+
+        biquad.prepare(48000, 1);
+
+        let mut samples: Vec<f32> = (0..48000)
+            .map(|index| (2.0 * std::f32::consts::PI * 1000.0 * index as f32 / 48000.0).sin())
+            .collect();
+
+        biquad.process(&mut samples);
+
+        assert!((peak(&samples[24000..]) - 10f32.powf(6.0 / 20.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn equalizer_prepares_added_bands() {
+        let mut equalizer = Equalizer::new();
+
+        equalizer.prepare(44100, 1);
+        equalizer.add_band(Biquad::new(BiquadKind::HighPass, 100.0, 0.707));
+
+        let mut samples = vec![1.0; 44100];
+
+        equalizer.process(&mut samples);
+
+        assert_eq!(equalizer.get_band_count(), 1);
+        assert!(samples[samples.len() - 1].abs() < 1e-4);
+    }
+}
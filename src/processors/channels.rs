@@ -0,0 +1,268 @@
+use crate::Processor;
+
+/// Widens or narrows the stereo image of the first two channels.
+///
+/// A width of 0 folds them to mono, 1 leaves them unchanged and more than 1 widens them.
+#[derive(Debug, Clone)]
+pub struct StereoWidth {
+    width: f32,
+    channels: usize,
+}
+
+impl StereoWidth {
+    pub fn new(width: f32) -> StereoWidth {
+        StereoWidth {
+            width: width.max(0.0),
+            channels: 0,
+        }
+    }
+
+    pub fn get_width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width.max(0.0);
+    }
+}
+
+impl Processor for StereoWidth {
+    fn prepare(&mut self, _sample_rate: u32, channels: usize) {
+        self.channels = channels;
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if self.channels < 2 {
+            return;
+        }
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            let mid = (frame[0] + frame[1]) / 2.0;
+            let side = (frame[0] - frame[1]) / 2.0 * self.width;
+
+            frame[0] = mid + side;
+            frame[1] = mid - side;
+        }
+    }
+}
+
+/// Swaps two channels, by default the left and right ones.
+#[derive(Debug, Clone)]
+pub struct ChannelSwap {
+    first: usize,
+    second: usize,
+    channels: usize,
+}
+
+impl Default for ChannelSwap {
+    fn default() -> ChannelSwap {
+        ChannelSwap::new(0, 1)
+    }
+}
+
+impl ChannelSwap {
+    pub fn new(first: usize, second: usize) -> ChannelSwap {
+        ChannelSwap {
+            first,
+            second,
+            channels: 0,
+        }
+    }
+
+    pub fn get_channels(&self) -> (usize, usize) {
+        (self.first, self.second)
+    }
+
+    pub fn set_channels(&mut self, first: usize, second: usize) {
+        self.first = first;
+        self.second = second;
+    }
+}
+
+impl Processor for ChannelSwap {
+    fn prepare(&mut self, _sample_rate: u32, channels: usize) {
+        self.channels = channels;
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if self.first.max(self.second) >= self.channels {
+            return;
+        }
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            frame.swap(self.first, self.second);
+        }
+    }
+}
+
+/// Silences individual channels.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelMute {
+    muted: Vec<bool>,
+    channels: usize,
+}
+
+impl ChannelMute {
+    pub fn new() -> ChannelMute {
+        ChannelMute::default()
+    }
+
+    pub fn is_muted(&self, channel: usize) -> bool {
+        self.muted.get(channel).copied().unwrap_or(false)
+    }
+
+    pub fn set_muted(&mut self, channel: usize, muted: bool) {
+        if channel >= self.muted.len() {
+            self.muted.resize(channel + 1, false);
+        }
+
+        self.muted[channel] = muted;
+    }
+}
+
+impl Processor for ChannelMute {
+    fn prepare(&mut self, _sample_rate: u32, channels: usize) {
+        self.channels = channels;
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if self.channels == 0 || !self.muted.contains(&true) {
+            return;
+        }
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            for (sample, &muted) in frame.iter_mut().zip(&self.muted) {
+                if muted {
+                    *sample = 0.0;
+                }
+            }
+        }
+    }
+}
+
+/// Replaces every channel with the average of all of them.
+#[derive(Debug, Clone)]
+pub struct MonoFoldDown {
+    enabled: bool,
+    channels: usize,
+}
+
+impl Default for MonoFoldDown {
+    fn default() -> MonoFoldDown {
+        MonoFoldDown::new()
+    }
+}
+
+impl MonoFoldDown {
+    pub fn new() -> MonoFoldDown {
+        MonoFoldDown {
+            enabled: true,
+            channels: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Processor for MonoFoldDown {
+    fn prepare(&mut self, _sample_rate: u32, channels: usize) {
+        self.channels = channels;
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if !self.enabled || self.channels < 2 {
+            return;
+        }
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            let mono = frame.iter().sum::<f32>() / self.channels as f32;
+
+            frame.fill(mono);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processed<P: Processor>(
+        mut processor: P,
+        channels: usize,
+        mut samples: Vec<f32>,
+    ) -> Vec<f32> {
+        processor.prepare(44100, channels);
+        processor.process(&mut samples);
+
+        samples
+    }
+
+    #[test]
+    fn zero_width_is_mono() {
+        let samples = processed(StereoWidth::new(0.0), 2, vec![1.0, 0.0, 0.25, -0.75]);
+
+        assert_eq!(samples, vec![0.5, 0.5, -0.25, -0.25]);
+    }
+
+    #[test]
+    fn unit_width_is_unchanged() {
+        let samples = processed(StereoWidth::new(1.0), 2, vec![1.0, 0.0, 0.25, -0.5]);
+
+        assert_eq!(samples, vec![1.0, 0.0, 0.25, -0.5]);
+    }
+
+    #[test]
+    fn swaps_channels() {
+        let samples = processed(ChannelSwap::default(), 2, vec![1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(samples, vec![2.0, 1.0, 4.0, 3.0]);
+
+        let samples = processed(
+            ChannelSwap::new(0, 2),
+            3,
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+
+        assert_eq!(samples, vec![3.0, 2.0, 1.0, 6.0, 5.0, 4.0]);
+    }
+
+    #[test]
+    fn swap_ignores_missing_channels() {
+        let samples = processed(ChannelSwap::new(0, 2), 2, vec![1.0, 2.0]);
+
+        assert_eq!(samples, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn mutes_channels() {
+        let mut mute = ChannelMute::new();
+
+        mute.set_muted(1, true);
+
+        assert!(mute.is_muted(1));
+        assert!(!mute.is_muted(0));
+
+        let samples = processed(mute, 2, vec![1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(samples, vec![1.0, 0.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn folds_down_to_mono() {
+        let samples = processed(MonoFoldDown::new(), 3, vec![1.0, 2.0, 3.0, 0.0, 0.0, 1.5]);
+
+        assert_eq!(samples, vec![2.0, 2.0, 2.0, 0.5, 0.5, 0.5]);
+
+        let mut disabled = MonoFoldDown::new();
+
+        disabled.set_enabled(false);
+
+        assert_eq!(processed(disabled, 2, vec![1.0, 3.0]), vec![1.0, 3.0]);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::processors::{db_to_gain, gain_to_db, time_coefficient};
+use crate::Processor;
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A feed-forward compressor, linked across channels, with an optional lookahead delay.
+///
+/// The lookahead delays the output by its length, the gain reduction follows the loudest peak within it so it can start before the peak arrives.
+#[derive(Debug, Clone)]
+pub struct Compressor {
+    threshold: f32,
+    ratio: f32,
+    attack: Duration,
+    release: Duration,
+    makeup_gain: f32,
+    lookahead: Duration,
+    sample_rate: u32,
+    channels: usize,
+    attack_coefficient: f32,
+    release_coefficient: f32,
+    delay: Vec<f32>,
+    delay_position: usize,
+    held_targets: VecDeque<(u64, f32)>,
+    frame_index: u64,
+    gain_reduction: f32,
+}
+
+impl Compressor {
+    /// Creates a compressor with a threshold in dB and a ratio of at least 1.
+    pub fn new(threshold: f32, ratio: f32) -> Compressor {
+        let mut compressor = Compressor {
+            threshold,
+            ratio: ratio.max(1.0),
+            attack: Duration::from_millis(5),
+            release: Duration::from_millis(100),
+            makeup_gain: 0.0,
+            lookahead: Duration::ZERO,
+            sample_rate: 44100,
+            channels: 0,
+            attack_coefficient: 0.0,
+            release_coefficient: 0.0,
+            delay: Vec::new(),
+            delay_position: 0,
+            held_targets: VecDeque::new(),
+            frame_index: 0,
+            gain_reduction: 0.0,
+        };
+
+        compressor.update_coefficients();
+
+        compressor
+    }
+
+    /// Creates a limiter keeping peaks at or below the threshold in dB.
+    ///
+    /// The attack is a fifth of the lookahead, and peaks the envelope doesn't fully catch are still held at the threshold.
+    pub fn limiter(threshold: f32, lookahead: Duration) -> Compressor {
+        let mut limiter = Compressor::new(threshold, f32::INFINITY);
+
+        limiter.attack = lookahead / 5;
+        limiter.release = Duration::from_millis(50);
+        limiter.lookahead = lookahead;
+        limiter.update_coefficients();
+
+        limiter
+    }
+
+    pub fn get_threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    pub fn get_ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    pub fn get_attack(&self) -> Duration {
+        self.attack
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) {
+        self.attack = attack;
+        self.update_coefficients();
+    }
+
+    pub fn get_release(&self) -> Duration {
+        self.release
+    }
+
+    pub fn set_release(&mut self, release: Duration) {
+        self.release = release;
+        self.update_coefficients();
+    }
+
+    pub fn get_makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain;
+    }
+
+    pub fn get_lookahead(&self) -> Duration {
+        self.lookahead
+    }
+
+    /// Changes the lookahead, the delayed samples are dropped.
+    pub fn set_lookahead(&mut self, lookahead: Duration) {
+        self.lookahead = lookahead;
+        self.reset_delay();
+    }
+
+    /// Returns the current gain reduction in dB, for metering.
+    pub fn get_gain_reduction(&self) -> f32 {
+        self.gain_reduction
+    }
+
+    fn update_coefficients(&mut self) {
+        self.attack_coefficient = time_coefficient(self.attack, self.sample_rate);
+        self.release_coefficient = time_coefficient(self.release, self.sample_rate);
+    }
+
+    fn reset_delay(&mut self) {
+        let frames = (self.lookahead.as_secs_f32() * self.sample_rate as f32).round() as usize;
+
+        self.delay = vec![0.0; frames * self.channels];
+        self.delay_position = 0;
+        self.held_targets.clear();
+    }
+
+    fn target(&self, frame: &[f32], slope: f32) -> f32 {
+        let peak = frame
+            .iter()
+            .fold(0f32, |peak, sample| peak.max(sample.abs()));
+        let over = gain_to_db(peak) - self.threshold;
+
+        if over > 0.0 {
+            over * slope
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Processor for Compressor {
+    fn prepare(&mut self, sample_rate: u32, channels: usize) {
+        self.sample_rate = sample_rate.max(1);
+        self.channels = channels;
+        self.gain_reduction = 0.0;
+        self.update_coefficients();
+        self.reset_delay();
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if self.channels == 0 {
+            return;
+        }
+
+        let slope = 1.0 - 1.0 / self.ratio;
+        let window = (self.delay.len() / self.channels) as u64 + 1;
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            let target = self.target(frame, slope);
+
+            // The largest target of the frames still in the delay line, kept in decreasing order.
+            while matches!(self.held_targets.back(), Some(&(_, held)) if held <= target) {
+                self.held_targets.pop_back();
+            }
+
+            self.held_targets.push_back((self.frame_index, target));
+
+            while matches!(self.held_targets.front(), Some(&(index, _)) if index + window <= self.frame_index)
+            {
+                self.held_targets.pop_front();
+            }
+
+            self.frame_index += 1;
+
+            let target = self.held_targets.front().map_or(target, |&(_, held)| held);
+
+            let coefficient = if target > self.gain_reduction {
+                self.attack_coefficient
+            } else {
+                self.release_coefficient
+            };
+
+            self.gain_reduction = coefficient * self.gain_reduction + (1.0 - coefficient) * target;
+
+            if !self.delay.is_empty() {
+                let delayed = &mut self.delay[self.delay_position..][..self.channels];
+
+                frame.swap_with_slice(delayed);
+
+                self.delay_position = (self.delay_position + self.channels) % self.delay.len();
+            }
+
+            let mut gain_reduction = self.gain_reduction;
+
+            if self.ratio.is_infinite() {
+                gain_reduction = gain_reduction.max(self.target(frame, slope));
+            }
+
+            let gain = db_to_gain(self.makeup_gain - gain_reduction);
+
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limiter_holds_step_below_threshold() {
+        let threshold = -12.0;
+        let mut limiter = Compressor::limiter(threshold, Duration::from_millis(5));
+
+        limiter.prepare(44100, 2);
+
+        let mut samples = vec![0.0; 2 * 1000];
+        samples.resize(2 * 21000, 1.0);
+
+        limiter.process(&mut samples);
+
+        let ceiling = db_to_gain(threshold) * (1.0 + 1e-5);
+
+        assert!(samples.iter().all(|sample| sample.abs() <= ceiling));
+        assert!((samples[samples.len() - 1] - db_to_gain(threshold)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn limiter_without_lookahead_holds_threshold() {
+        let threshold = -6.0;
+        let mut limiter = Compressor::limiter(threshold, Duration::ZERO);
+
+        limiter.prepare(48000, 1);
+
+        let mut samples: Vec<f32> = (0..4800).map(|index| (index as f32 * 0.05).sin()).collect();
+
+        limiter.process(&mut samples);
+
+        let ceiling = db_to_gain(threshold) * (1.0 + 1e-5);
+
+        assert!(samples.iter().all(|sample| sample.abs() <= ceiling));
+    }
+
+    #[test]
+    fn compressor_applies_ratio() {
+        let mut compressor = Compressor::new(-20.0, 4.0);
+
+        compressor.prepare(44100, 1);
+
+        // 20 dB over the threshold settles at 5 dB over it.
+        let mut samples = vec![1.0; 44100];
+
+        compressor.process(&mut samples);
+
+        let level = gain_to_db(samples[samples.len() - 1]);
+
+        assert!((level - -15.0).abs() < 0.01, "level {}", level);
+    }
+
+    #[test]
+    fn lookahead_delays_output() {
+        let mut compressor = Compressor::new(0.0, 1.0);
+
+        compressor.set_lookahead(Duration::from_millis(1));
+        compressor.prepare(10000, 1);
+
+        let mut samples = vec![0.0; 20];
+        samples[0] = 0.5;
+
+        compressor.process(&mut samples);
+
+        assert_eq!(samples[10], 0.5);
+        assert!(samples
+            .iter()
+            .enumerate()
+            .all(|(index, &sample)| index == 10 || sample == 0.0));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Processor;
+
+use std::f32::consts::PI;
+
+/// Removes DC offset with a one-pole high-pass filter.
+#[derive(Debug, Clone)]
+pub struct DcBlocker {
+    cutoff: f32,
+    sample_rate: u32,
+    coefficient: f32,
+    state: Vec<[f32; 2]>,
+}
+
+impl Default for DcBlocker {
+    fn default() -> DcBlocker {
+        DcBlocker::new(10.0)
+    }
+}
+
+impl DcBlocker {
+    /// Creates a DC blocker with a cutoff frequency in Hz, low enough not to touch audible content.
+    pub fn new(cutoff: f32) -> DcBlocker {
+        let mut blocker = DcBlocker {
+            cutoff,
+            sample_rate: 44100,
+            coefficient: 0.0,
+            state: Vec::new(),
+        };
+
+        blocker.update();
+
+        blocker
+    }
+
+    pub fn get_cutoff(&self) -> f32 {
+        self.cutoff
+    }
+
+    pub fn set_cutoff(&mut self, cutoff: f32) {
+        self.cutoff = cutoff;
+        self.update();
+    }
+
+    fn update(&mut self) {
+        self.coefficient = (-2.0 * PI * self.cutoff.max(0.0) / self.sample_rate as f32).exp();
+    }
+}
+
+impl Processor for DcBlocker {
+    fn prepare(&mut self, sample_rate: u32, channels: usize) {
+        self.sample_rate = sample_rate.max(1);
+        self.state = vec![[0.0; 2]; channels];
+        self.update();
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        let channels = self.state.len().max(1);
+
+        for frame in samples.chunks_mut(channels) {
+            for (sample, state) in frame.iter_mut().zip(self.state.iter_mut()) {
+                let output = *sample - state[0] + self.coefficient * state[1];
+
+                state[0] = *sample;
+                state[1] = output;
+
+                *sample = output;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_constant_offset() {
+        let mut blocker = DcBlocker::default();
+
+        blocker.prepare(44100, 2);
+
+        let mut samples = vec![0.5; 2 * 44100];
+
+        blocker.process(&mut samples);
+
+        assert!(samples[samples.len() - 2..]
+            .iter()
+            .all(|sample| sample.abs() < 1e-3));
+    }
+
+    #[test]
+    fn keeps_audible_tone() {
+        let mut blocker = DcBlocker::default();
+
+        blocker.prepare(44100, 1);
+
+        let mut samples: Vec<f32> = (0..44100)
+            .map(|index| 0.25 + (2.0 * std::f32::consts::PI * 440.0 * index as f32 / 44100.0).sin())
+            .collect();
+
+        blocker.process(&mut samples);
+
+        let tail = &samples[22050..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        let peak = tail
+            .iter()
+            .fold(0f32, |peak, sample| peak.max(sample.abs()));
+
+        assert!(mean.abs() < 0.01);
+        assert!((peak - 1.0).abs() < 0.01);
+    }
+}
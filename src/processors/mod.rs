@@ -0,0 +1,162 @@
+mod biquad;
+mod channels;
+mod compressor;
+mod dc_blocker;
+mod noise_gate;
+mod soft_clipper;
+
+pub use biquad::*;
+pub use channels::*;
+pub use compressor::*;
+pub use dc_blocker::*;
+pub use noise_gate::*;
+pub use soft_clipper::*;
+
+use crate::dsp::dsp_format;
+use crate::sample::{f32_to_i16, f32_to_u8, i16_to_f32, u8_to_f32};
+use crate::{BassError, ChannelInfo, DspHandle, SampleFormat};
+
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::time::Duration;
+
+use bass_sys::*;
+
+/// An effect processing interleaved floating-point samples in Rust, see `Stream::add_processor`.
+pub trait Processor: Send + 'static {
+    /// Called with the format of the channel before any samples are processed.
+    fn prepare(&mut self, sample_rate: u32, channels: usize);
+
+    fn process(&mut self, samples: &mut [f32]);
+}
+
+/// A processor applied to a channel, it's removed when dropped.
+pub struct ProcessorHandle<P: Processor> {
+    processor: Arc<Mutex<P>>,
+    dsp: DspHandle,
+}
+
+impl<P: Processor> ProcessorHandle<P> {
+    pub(crate) fn new(
+        channel: DWORD,
+        info: &ChannelInfo,
+        priority: i32,
+        mut processor: P,
+    ) -> Result<ProcessorHandle<P>, BassError> {
+        processor.prepare(info.get_frequency(), info.get_channels() as usize);
+
+        let native_format = info.get_sample_format();
+        let processor = Arc::new(Mutex::new(processor));
+        let shared = processor.clone();
+        let mut buffer = Vec::new();
+
+        // Integer data is converted, so the processors only have to deal with floats.
+        let dsp = match dsp_format(native_format)? {
+            SampleFormat::Float => DspHandle::new(
+                channel,
+                native_format,
+                priority,
+                move |samples: &mut [f32]| {
+                    if let Some(mut processor) = try_lock(&shared) {
+                        processor.process(samples);
+                    }
+                },
+            )?,
+            SampleFormat::Int16 => DspHandle::new(
+                channel,
+                native_format,
+                priority,
+                move |samples: &mut [i16]| {
+                    let mut processor = match try_lock(&shared) {
+                        Some(processor) => processor,
+                        None => return,
+                    };
+
+                    buffer.clear();
+                    buffer.extend(samples.iter().map(|&sample| i16_to_f32(sample)));
+
+                    processor.process(&mut buffer);
+
+                    for (sample, &value) in samples.iter_mut().zip(&buffer) {
+                        *sample = f32_to_i16(value);
+                    }
+                },
+            )?,
+            SampleFormat::Int8 => DspHandle::new(
+                channel,
+                native_format,
+                priority,
+                move |samples: &mut [u8]| {
+                    let mut processor = match try_lock(&shared) {
+                        Some(processor) => processor,
+                        None => return,
+                    };
+
+                    buffer.clear();
+                    buffer.extend(samples.iter().map(|&sample| u8_to_f32(sample)));
+
+                    processor.process(&mut buffer);
+
+                    for (sample, &value) in samples.iter_mut().zip(&buffer) {
+                        *sample = f32_to_u8(value);
+                    }
+                },
+            )?,
+        };
+
+        Ok(ProcessorHandle { processor, dsp })
+    }
+
+    /// Gives access to the processor to change its parameters or read its state.
+    ///
+    /// The mixing thread doesn't wait for the guard, the samples it gets while the guard is held pass through unprocessed, so it should be dropped quickly.
+    pub fn lock(&self) -> MutexGuard<'_, P> {
+        lock(&self.processor)
+    }
+
+    pub fn set_priority(&self, priority: i32) -> Result<(), BassError> {
+        self.dsp.set_priority(priority)
+    }
+}
+
+fn lock<P>(processor: &Mutex<P>) -> MutexGuard<'_, P> {
+    processor.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// Locks the processor unless it's already locked, the mixing thread never waits for `ProcessorHandle::lock`.
+fn try_lock<P>(processor: &Mutex<P>) -> Option<MutexGuard<'_, P>> {
+    match processor.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+pub(crate) fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+pub(crate) fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(1e-10).log10()
+}
+
+/// Returns the coefficient of a one-pole smoother reaching about 63% of a step in `time`.
+pub(crate) fn time_coefficient(time: Duration, sample_rate: u32) -> f32 {
+    let samples = time.as_secs_f32() * sample_rate as f32;
+
+    if samples < 1.0 {
+        return 0.0;
+    }
+
+    (-1.0 / samples).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decibels_convert_both_ways() {
+        assert!((db_to_gain(-6.0) - 0.501).abs() < 1e-3);
+        assert!((gain_to_db(db_to_gain(-12.5)) - -12.5).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::processors::{gain_to_db, time_coefficient};
+use crate::Processor;
+
+use std::time::Duration;
+
+/// Silences the signal while its level, linked across channels, stays below the threshold in dB.
+#[derive(Debug, Clone)]
+pub struct NoiseGate {
+    threshold: f32,
+    attack: Duration,
+    hold: Duration,
+    release: Duration,
+    sample_rate: u32,
+    channels: usize,
+    attack_coefficient: f32,
+    release_coefficient: f32,
+    hold_frames: usize,
+    hold_remaining: usize,
+    gain: f32,
+}
+
+impl NoiseGate {
+    pub fn new(threshold: f32) -> NoiseGate {
+        let mut gate = NoiseGate {
+            threshold,
+            attack: Duration::from_millis(1),
+            hold: Duration::from_millis(50),
+            release: Duration::from_millis(100),
+            sample_rate: 44100,
+            channels: 0,
+            attack_coefficient: 0.0,
+            release_coefficient: 0.0,
+            hold_frames: 0,
+            hold_remaining: 0,
+            gain: 1.0,
+        };
+
+        gate.update_coefficients();
+
+        gate
+    }
+
+    pub fn get_threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    pub fn get_attack(&self) -> Duration {
+        self.attack
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) {
+        self.attack = attack;
+        self.update_coefficients();
+    }
+
+    /// Returns how long the gate stays open after the level falls below the threshold.
+    pub fn get_hold(&self) -> Duration {
+        self.hold
+    }
+
+    pub fn set_hold(&mut self, hold: Duration) {
+        self.hold = hold;
+        self.update_coefficients();
+    }
+
+    pub fn get_release(&self) -> Duration {
+        self.release
+    }
+
+    pub fn set_release(&mut self, release: Duration) {
+        self.release = release;
+        self.update_coefficients();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.hold_remaining > 0
+    }
+
+    fn update_coefficients(&mut self) {
+        self.attack_coefficient = time_coefficient(self.attack, self.sample_rate);
+        self.release_coefficient = time_coefficient(self.release, self.sample_rate);
+        self.hold_frames = (self.hold.as_secs_f32() * self.sample_rate as f32).round() as usize;
+    }
+}
+
+impl Processor for NoiseGate {
+    fn prepare(&mut self, sample_rate: u32, channels: usize) {
+        self.sample_rate = sample_rate.max(1);
+        self.channels = channels;
+        self.update_coefficients();
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if self.channels == 0 {
+            return;
+        }
+
+        for frame in samples.chunks_exact_mut(self.channels) {
+            let peak = frame
+                .iter()
+                .fold(0f32, |peak, sample| peak.max(sample.abs()));
+
+            if gain_to_db(peak) >= self.threshold {
+                self.hold_remaining = self.hold_frames.max(1);
+            } else {
+                self.hold_remaining = self.hold_remaining.saturating_sub(1);
+            }
+
+            let (target, coefficient) = if self.hold_remaining > 0 {
+                (1.0, self.attack_coefficient)
+            } else {
+                (0.0, self.release_coefficient)
+            };
+
+            self.gain = coefficient * self.gain + (1.0 - coefficient) * target;
+
+            for sample in frame {
+                *sample *= self.gain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn silences_signal_below_threshold() {
+        let mut gate = NoiseGate::new(-40.0);
+
+        gate.prepare(44100, 2);
+
+        // A second covers the 50 ms hold and many times the 100 ms release.
+        let mut samples = vec![0.001; 2 * 44100];
+
+        gate.process(&mut samples);
+
+        assert!(!gate.is_open());
+        assert!(samples[samples.len() - 2..]
+            .iter()
+            .all(|sample| sample.abs() < 1e-7));
+    }
+
+    #[test]
+    fn holds_before_release() {
+        let mut gate = NoiseGate::new(-40.0);
+
+        gate.prepare(1000, 1);
+
+        let mut samples = vec![0.5; 10];
+        samples.resize(40, 0.001);
+
+        gate.process(&mut samples);
+
+        // The 50 ms hold at 1000 Hz keeps the quiet samples untouched at first.
+        assert_eq!(samples[9], 0.5);
+        assert!((samples[30] - 0.001).abs() < 1e-6);
+    }
+
+    #[test]
+    fn passes_signal_above_threshold() {
+        let mut gate = NoiseGate::new(-40.0);
+
+        gate.prepare(44100, 1);
+
+        let mut samples = vec![0.5; 4410];
+
+        gate.process(&mut samples);
+
+        assert!(gate.is_open());
+        assert!((samples[samples.len() - 1] - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::processors::db_to_gain;
+use crate::Processor;
+
+/// Saturates the signal smoothly towards the ceiling instead of clipping it hard.
+#[derive(Debug, Clone)]
+pub struct SoftClipper {
+    drive: f32,
+    ceiling: f32,
+    drive_gain: f32,
+}
+
+impl SoftClipper {
+    /// Creates a soft clipper with a drive in dB applied before the curve and a linear ceiling.
+    pub fn new(drive: f32, ceiling: f32) -> SoftClipper {
+        SoftClipper {
+            drive,
+            ceiling: ceiling.max(f32::EPSILON),
+            drive_gain: db_to_gain(drive),
+        }
+    }
+
+    pub fn get_drive(&self) -> f32 {
+        self.drive
+    }
+
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive;
+        self.drive_gain = db_to_gain(drive);
+    }
+
+    pub fn get_ceiling(&self) -> f32 {
+        self.ceiling
+    }
+
+    pub fn set_ceiling(&mut self, ceiling: f32) {
+        self.ceiling = ceiling.max(f32::EPSILON);
+    }
+}
+
+impl Processor for SoftClipper {
+    fn prepare(&mut self, _sample_rate: u32, _channels: usize) {}
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            *sample = self.ceiling * (*sample * self.drive_gain / self.ceiling).tanh();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_within_ceiling() {
+        let mut clipper = SoftClipper::new(12.0, 0.8);
+
+        clipper.prepare(44100, 1);
+
+        let mut samples: Vec<f32> = (-1000..=1000).map(|index| index as f32 / 100.0).collect();
+
+        clipper.process(&mut samples);
+
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.8));
+    }
+
+    #[test]
+    fn keeps_quiet_signal_nearly_linear() {
+        let mut clipper = SoftClipper::new(0.0, 1.0);
+
+        clipper.prepare(44100, 1);
+
+        let mut samples = vec![0.01, -0.01];
+
+        clipper.process(&mut samples);
+
+        assert!((samples[0] - 0.01).abs() < 1e-5);
+        assert!((samples[1] + 0.01).abs() < 1e-5);
+    }
+}
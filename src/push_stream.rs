@@ -0,0 +1,137 @@
+use crate::reader::Unseekable;
+use crate::{Bass, BassError, FileSystem, Sample, Stream, StreamBuilder};
+
+use std::ffi::c_void;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Deref;
+use std::ptr;
+
+use bass_sys::*;
+
+/// A stream playing PCM samples pushed to it, see `STREAMPROC_PUSH`.
+pub struct PushStream<T: Sample> {
+    stream: Stream,
+    _sample: PhantomData<T>,
+}
+
+impl<T: Sample> Deref for PushStream<T> {
+    type Target = Stream;
+
+    fn deref(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+impl<T: Sample> PushStream<T> {
+    pub fn create(bass: &Bass, frequency: u32, channels: u32) -> Result<PushStream<T>, BassError> {
+        StreamBuilder::new(bass).create_push_stream(frequency, channels)
+    }
+
+    pub(crate) fn from_stream(stream: Stream) -> PushStream<T> {
+        PushStream {
+            stream,
+            _sample: PhantomData,
+        }
+    }
+
+    /// Queues the interleaved samples and returns the number of samples queued in total.
+    pub fn push(&self, samples: &[T]) -> Result<usize, BassError> {
+        if mem::size_of_val(samples) > (BASS_STREAMPROC_END - 1) as usize {
+            return Err(BassError::ValueOutOfRange);
+        }
+
+        self.put_data(
+            samples.as_ptr() as *const c_void,
+            mem::size_of_val(samples) as DWORD,
+        )
+    }
+
+    /// Marks the end of the stream, it ends once the queued samples are played.
+    pub fn end(&self) -> Result<(), BassError> {
+        self.put_data(ptr::null(), BASS_STREAMPROC_END)?;
+
+        Ok(())
+    }
+
+    pub fn get_queued(&self) -> Result<usize, BassError> {
+        self.put_data(ptr::null(), 0)
+    }
+
+    fn put_data(&self, buffer: *const c_void, length: DWORD) -> Result<usize, BassError> {
+        let queued = BASS_StreamPutData(*self.stream.get_raw_handle(), buffer, length);
+
+        if queued == DWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(queued as usize / mem::size_of::<T>())
+    }
+}
+
+/// A buffered user file stream whose file data is pushed to it, see `STREAMFILE_BUFFERPUSH`.
+pub struct PushFileStream {
+    stream: Stream,
+}
+
+impl Deref for PushFileStream {
+    type Target = Stream;
+
+    fn deref(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+impl PushFileStream {
+    /// Creates the stream, `reader` only provides the data required to create it.
+    pub fn create<R>(bass: &Bass, reader: R) -> Result<PushFileStream, BassError>
+    where
+        R: Read + Send + 'static,
+    {
+        StreamBuilder::new(bass).create_push_file_stream(reader)
+    }
+
+    pub(crate) fn from_reader<R>(
+        builder: StreamBuilder,
+        reader: R,
+    ) -> Result<PushFileStream, BassError>
+    where
+        R: Read + Send + 'static,
+    {
+        let stream = builder.create_from_reader(Unseekable::new(reader), FileSystem::BufferPush)?;
+
+        Ok(PushFileStream { stream })
+    }
+
+    /// Pushes file data and returns the number of bytes that were accepted.
+    pub fn push(&self, data: &[u8]) -> Result<usize, BassError> {
+        // A length of 0 is `BASS_FILEDATA_END`, only `end` should end the file.
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        if data.len() > (DWORD::MAX - 1) as usize {
+            return Err(BassError::ValueOutOfRange);
+        }
+
+        self.put_file_data(data.as_ptr() as *const c_void, data.len() as DWORD)
+    }
+
+    /// Marks the end of the file.
+    pub fn end(&self) -> Result<(), BassError> {
+        self.put_file_data(ptr::null(), BASS_FILEDATA_END)?;
+
+        Ok(())
+    }
+
+    fn put_file_data(&self, buffer: *const c_void, length: DWORD) -> Result<usize, BassError> {
+        let accepted = BASS_StreamPutFileData(*self.stream.get_raw_handle(), buffer, length);
+
+        if accepted == DWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(accepted as usize)
+    }
+}
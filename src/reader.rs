@@ -0,0 +1,133 @@
+use std::ffi::c_void;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use bass_sys::*;
+
+/// How BASS reads from a user file, see the `STREAMFILE_*` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileSystem {
+    /// The file is read on demand, seeking is supported.
+    NoBuffer,
+    /// The file is read in the background into a buffer, like internet files.
+    Buffer,
+    /// Like `Buffer`, but only the data needed for the creation is read, the rest has to be pushed.
+    BufferPush,
+}
+
+impl FileSystem {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            FileSystem::NoBuffer => STREAMFILE_NOBUFFER,
+            FileSystem::Buffer => STREAMFILE_BUFFER,
+            FileSystem::BufferPush => STREAMFILE_BUFFERPUSH,
+        }
+    }
+}
+
+pub(crate) fn file_procs<R: Read + Seek>() -> BassFileProcs {
+    BassFileProcs::new(
+        close_proc as *mut FILECLOSEPROC,
+        length_proc::<R> as *mut FILELENPROC,
+        read_proc::<R> as *mut FILEREADPROC,
+        seek_proc::<R> as *mut FILESEEKPROC,
+    )
+}
+
+// The reader is owned by the stream and dropped after it's freed, so there's nothing to close here.
+extern "system" fn close_proc(_user: *mut c_void) {}
+
+extern "system" fn length_proc<R: Read + Seek>(user: *mut c_void) -> QWORD {
+    let reader = unsafe { &mut *(user as *mut R) };
+
+    let length = panic::catch_unwind(AssertUnwindSafe(|| {
+        let current = reader.stream_position()?;
+        let end = reader.seek(SeekFrom::End(0))?;
+
+        reader.seek(SeekFrom::Start(current))?;
+
+        Ok::<u64, std::io::Error>(end)
+    }));
+
+    match length {
+        Ok(Ok(length)) => length,
+        _ => 0,
+    }
+}
+
+extern "system" fn read_proc<R: Read + Seek>(
+    buffer: *mut c_void,
+    length: DWORD,
+    user: *mut c_void,
+) -> DWORD {
+    let reader = unsafe { &mut *(user as *mut R) };
+    let buffer = unsafe { slice::from_raw_parts_mut(buffer as *mut u8, length as usize) };
+
+    let read = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut read = 0;
+
+        while read < buffer.len() {
+            match reader.read(&mut buffer[read..]) {
+                Ok(0) => break,
+                Ok(count) => read += count,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) if read == 0 => return Err(error),
+                Err(_) => break,
+            }
+        }
+
+        Ok(read)
+    }));
+
+    match read {
+        Ok(Ok(read)) => read as DWORD,
+        _ => DWORD::MAX,
+    }
+}
+
+extern "system" fn seek_proc<R: Read + Seek>(offset: QWORD, user: *mut c_void) -> BOOL {
+    let reader = unsafe { &mut *(user as *mut R) };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| reader.seek(SeekFrom::Start(offset))));
+
+    match result {
+        Ok(Ok(_)) => 1,
+        _ => 0,
+    }
+}
+
+/// Adapts a reader that can't seek, its length is reported as unknown.
+pub(crate) struct Unseekable<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: Read> Unseekable<R> {
+    pub(crate) fn new(reader: R) -> Unseekable<R> {
+        Unseekable {
+            reader,
+            position: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for Unseekable<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buffer)?;
+
+        self.position += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl<R: Read> Seek for Unseekable<R> {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        match position {
+            SeekFrom::Start(offset) if offset == self.position => Ok(self.position),
+            SeekFrom::Current(0) => Ok(self.position),
+            _ => Err(ErrorKind::Unsupported.into()),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use bass_sys::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    Float,
+    Int16,
+    Int8,
+}
+
+impl SampleFormat {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            SampleFormat::Float => BASS_SAMPLE_FLOAT,
+            SampleFormat::Int16 => 0,
+            SampleFormat::Int8 => BASS_SAMPLE_8BITS,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        match self {
+            SampleFormat::Float => 4,
+            SampleFormat::Int16 => 2,
+            SampleFormat::Int8 => 1,
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for i16 {}
+    impl Sealed for u8 {}
+}
+
+/// A sample type BASS can work with, 8-bit samples are unsigned.
+pub trait Sample: private::Sealed + Copy + Default + Send + 'static {
+    const FORMAT: SampleFormat;
+}
+
+impl Sample for f32 {
+    const FORMAT: SampleFormat = SampleFormat::Float;
+}
+
+impl Sample for i16 {
+    const FORMAT: SampleFormat = SampleFormat::Int16;
+}
+
+impl Sample for u8 {
+    const FORMAT: SampleFormat = SampleFormat::Int8;
+}
+
+pub(crate) fn i16_to_f32(sample: i16) -> f32 {
+    sample as f32 / 32768.0
+}
+
+pub(crate) fn f32_to_i16(value: f32) -> i16 {
+    (value * 32768.0).round().clamp(-32768.0, 32767.0) as i16
+}
+
+pub(crate) fn u8_to_f32(sample: u8) -> f32 {
+    (sample as f32 - 128.0) / 128.0
+}
+
+pub(crate) fn f32_to_u8(value: f32) -> u8 {
+    (value * 128.0 + 128.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int16_round_trip_is_lossless() {
+        for sample in i16::MIN..=i16::MAX {
+            assert_eq!(f32_to_i16(i16_to_f32(sample)), sample);
+        }
+    }
+
+    #[test]
+    fn int8_round_trip_is_lossless() {
+        for sample in u8::MIN..=u8::MAX {
+            assert_eq!(f32_to_u8(u8_to_f32(sample)), sample);
+        }
+    }
+
+    #[test]
+    fn conversions_clamp() {
+        assert_eq!(f32_to_i16(2.0), i16::MAX);
+        assert_eq!(f32_to_i16(-2.0), i16::MIN);
+        assert_eq!(f32_to_u8(2.0), u8::MAX);
+        assert_eq!(f32_to_u8(-2.0), u8::MIN);
+    }
+}
@@ -1,121 +1,141 @@
-use crate::BassError;
-
-#[cfg(target_family = "unix")]
-use std::ffi::CString;
-
+use crate::events::EventSyncs;
+#[cfg(feature = "async")]
+use crate::SyncFuture;
+use crate::{
+    Attribute, Bass, BassError, ChannelInfo, ChannelState, Device, DspHandle, FileSystem,
+    PlaybackEvent, Position, PositionFlags, PositionMode, Processor, ProcessorHandle, Sample,
+    SampleFormat, StreamBuilder, StreamStatus, SyncEvent, SyncFlags, SyncHandle, SyncKind,
+};
+
+use std::any::Any;
 use std::ffi::c_void;
+use std::io::{Read, Seek};
+use std::ptr;
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use bass_sys::*;
 
-#[cfg(target_os = "windows")]
-use widestring::U16CString;
-
 pub struct Stream {
     handle: HSTREAM,
+    bass: Bass,
+    events: Mutex<Option<EventSyncs>>,
+    owned: bool,
+    _data: Option<Box<dyn Any + Send + Sync>>,
 }
 
 impl Drop for Stream {
     fn drop(&mut self) {
-        BASS_StreamFree(self.handle);
+        if self.owned {
+            BASS_StreamFree(self.handle);
+        }
     }
 }
 
 impl Stream {
-    pub fn create_from_file(file_name: String) -> Result<Stream, BassError> {
-        let handle;
+    pub fn create_from_file(bass: &Bass, file_name: String) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass).create_from_file(file_name)
+    }
 
-        #[cfg(target_family = "windows")]
-        {
-            let file_name_raw = U16CString::from_str(file_name).unwrap();
-            let file_name_raw = file_name_raw.into_raw() as *const c_void;
+    pub fn create_from_url(bass: &Bass, url: String) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass).create_from_url(url)
+    }
 
-            handle = BASS_StreamCreateFile(0, file_name_raw, 0, 0, BASS_UNICODE);
-        }
+    pub fn create_from_memory(bass: &Bass, data: &'static [u8]) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass).create_from_memory(data)
+    }
 
-        #[cfg(target_family = "unix")]
-        {
-            let file_name_raw = CString::new(file_name).unwrap();
-            let file_name_raw = file_name_raw.as_ptr() as *const c_void;
-
-            handle = BASS_StreamCreateFile(0, file_name_raw, 0, 0, 0);
-        }
-
-        if handle == 0 {
-            let error_code = BASS_ErrorGetCode();
-
-            match error_code {
-                BASS_ERROR_FILEOPEN => return Err(BassError::FileCouldNotBeOpened),
-                BASS_ERROR_FILEFORM => return Err(BassError::InvalidFileFormat),
-                BASS_ERROR_NOTAUDIO => return Err(BassError::InvalidFileContent),
-                BASS_ERROR_CODEC => return Err(BassError::InvalidCodec),
-                BASS_ERROR_FORMAT => return Err(BassError::InvalidSampleFormat),
-                BASS_ERROR_MEM => return Err(BassError::InsufficientMemory),
-                _ => panic!(
-                    "Failed to create the stream, error code: {}",
-                    BASS_ErrorGetCode()
-                ),
-            }
-        }
+    /// Creates a stream from a buffer, which is kept alive as long as the stream.
+    pub fn create_from_owned_memory<T>(bass: &Bass, data: T) -> Result<Stream, BassError>
+    where
+        T: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        StreamBuilder::new(bass).create_from_owned_memory(data)
+    }
 
-        Ok(Stream { handle })
+    pub fn create_from_reader<R>(
+        bass: &Bass,
+        reader: R,
+        system: FileSystem,
+    ) -> Result<Stream, BassError>
+    where
+        R: Read + Seek + Send + 'static,
+    {
+        StreamBuilder::new(bass).create_from_reader(reader, system)
     }
 
-    pub fn create_from_url(url: String) -> Result<Stream, BassError> {
-        let handle;
+    pub fn create_with_callback<T, F>(
+        bass: &Bass,
+        frequency: u32,
+        channels: u32,
+        callback: F,
+    ) -> Result<Stream, BassError>
+    where
+        T: Sample,
+        F: FnMut(&mut [T]) -> StreamStatus + Send + 'static,
+    {
+        StreamBuilder::new(bass).create_with_callback(frequency, channels, callback)
+    }
 
-        #[cfg(target_family = "windows")]
-        {
-            let url_raw = U16CString::from_str(url).unwrap();
-            let url_raw = url_raw.into_raw() as *const c_void;
+    pub fn create_dummy(
+        bass: &Bass,
+        frequency: u32,
+        channels: u32,
+        format: SampleFormat,
+    ) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass)
+            .sample_format(format)
+            .create_dummy(frequency, channels)
+    }
 
-            handle = BASS_StreamCreateFile(0, url_raw, 0, 0, BASS_UNICODE);
+    pub fn create_device_output(bass: &Bass) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass).create_device_output()
+    }
 
-            unsafe { U16CString::from_raw(url_raw as *mut u16) };
+    pub fn create_device_output_3d(bass: &Bass) -> Result<Stream, BassError> {
+        StreamBuilder::new(bass).create_device_output_3d()
+    }
+
+    pub(crate) fn from_raw(handle: HSTREAM, bass: Bass) -> Stream {
+        Stream {
+            handle,
+            bass,
+            events: Mutex::new(None),
+            owned: true,
+            _data: None,
         }
+    }
 
-        #[cfg(target_family = "unix")]
-        {
-            let url_raw = CString::new(url).unwrap();
-            let url_raw = url_raw.as_ptr() as *const c_void;
-
-            handle = BASS_StreamCreateFile(0, url_raw, 0, 0, 0);
-
-            unsafe { CString::from_raw(url_raw as *mut i8) };
-        }
-
-        if handle == 0 {
-            let error_code = BASS_ErrorGetCode();
-
-            match error_code {
-                BASS_ERROR_NONET => return Err(BassError::NoInternetConnection),
-                BASS_ERROR_PROTOCOL => return Err(BassError::InvalidProtocol),
-                BASS_ERROR_SSL => return Err(BassError::SslSupportNotAvailable),
-                BASS_ERROR_TIMEOUT => return Err(BassError::TimeOut),
-                BASS_ERROR_FILEOPEN => return Err(BassError::FileCouldNotBeOpened),
-                BASS_ERROR_FILEFORM => return Err(BassError::InvalidFileFormat),
-                BASS_ERROR_UNSTREAMABLE => return Err(BassError::UnstreamableFile),
-                BASS_ERROR_NOTAUDIO => return Err(BassError::InvalidFileContent),
-                BASS_ERROR_CODEC => return Err(BassError::InvalidCodec),
-                BASS_ERROR_FORMAT => return Err(BassError::InvalidSampleFormat),
-                BASS_ERROR_MEM => return Err(BassError::InsufficientMemory),
-                _ => panic!(
-                    "Failed to create the stream, error code: {}",
-                    BASS_ErrorGetCode()
-                ),
-            }
+    /// Wraps a stream owned by BASS itself, it isn't freed when dropped.
+    pub(crate) fn from_raw_shared(handle: HSTREAM, bass: Bass) -> Stream {
+        Stream {
+            handle,
+            bass,
+            events: Mutex::new(None),
+            owned: false,
+            _data: None,
         }
+    }
 
-        Ok(Stream { handle })
+    /// Creates the stream with data that has to stay alive until the stream is freed.
+    pub(crate) fn from_raw_with_data(
+        handle: HSTREAM,
+        bass: Bass,
+        data: Box<dyn Any + Send + Sync>,
+    ) -> Stream {
+        Stream {
+            handle,
+            bass,
+            events: Mutex::new(None),
+            owned: true,
+            _data: Some(data),
+        }
     }
 
     pub fn play(&self) -> Result<(), BassError> {
         if BASS_ChannelPlay(self.handle, 0) == 0 {
-            let error_code = BASS_ErrorGetCode();
-
-            match error_code {
-                BASS_ERROR_START => return Err(BassError::OutputIsPausedOrStopped),
-                _ => panic!("Failed to play the stream, error code: {}", error_code),
-            }
+            return Err(BassError::last());
         }
 
         Ok(())
@@ -123,12 +143,7 @@ impl Stream {
 
     pub fn pause(&self) -> Result<(), BassError> {
         if BASS_ChannelPause(self.handle) == 0 {
-            let error_code = BASS_ErrorGetCode();
-
-            match error_code {
-                BASS_ERROR_NOPLAY => return Err(BassError::StreamIsNotPlaying),
-                _ => panic!("Failed to pause the stream, error code: {}", error_code),
-            }
+            return Err(BassError::last());
         }
 
         Ok(())
@@ -136,182 +151,538 @@ impl Stream {
 
     pub fn stop(&self) -> Result<(), BassError> {
         if BASS_ChannelStop(self.handle) == 0 {
-            panic!(
-                "Failed to stop the stream, error code: {}",
-                BASS_ErrorGetCode()
-            );
+            return Err(BassError::last());
         }
 
         Ok(())
     }
 
-    pub fn lock(&self) {
+    pub fn state(&self) -> Result<ChannelState, BassError> {
+        let state = BASS_ChannelIsActive(self.handle);
+
+        // Stopped is also returned on failure, the error code tells them apart.
+        if state == BASS_ACTIVE_STOPPED && BASS_ErrorGetCode() != BASS_OK {
+            return Err(BassError::last());
+        }
+
+        Ok(ChannelState::from_raw(state))
+    }
+
+    pub fn is_playing(&self) -> Result<bool, BassError> {
+        Ok(self.state()? == ChannelState::Playing)
+    }
+
+    /// Returns `true` if the stream was paused, either with `pause` or along with its device.
+    pub fn is_paused(&self) -> Result<bool, BassError> {
+        Ok(matches!(
+            self.state()?,
+            ChannelState::Paused | ChannelState::PausedDevice
+        ))
+    }
+
+    pub fn is_stopped(&self) -> Result<bool, BassError> {
+        Ok(self.state()? == ChannelState::Stopped)
+    }
+
+    pub fn is_stalled(&self) -> Result<bool, BassError> {
+        Ok(self.state()? == ChannelState::Stalled)
+    }
+
+    pub fn lock(&self) -> Result<(), BassError> {
         if BASS_ChannelLock(self.handle, 1) == 0 {
-            panic!(
-                "Failed to lock the stream, error code: {}",
-                BASS_ErrorGetCode()
-            );
+            return Err(BassError::last());
         }
+
+        Ok(())
     }
 
-    pub fn unlock(&self) {
+    pub fn unlock(&self) -> Result<(), BassError> {
         if BASS_ChannelLock(self.handle, 0) == 0 {
-            panic!(
-                "Failed to unlock the stream, error code: {}",
-                BASS_ErrorGetCode()
-            );
+            return Err(BassError::last());
         }
+
+        Ok(())
     }
 
-    pub fn get_bit_rate(&self) -> f32 {
-        let mut bit_rate = 0.0f32;
+    /// Moves the stream to another initialized device, keeping its position and attributes.
+    pub fn set_device(&mut self, bass: &Bass) -> Result<(), BassError> {
+        if BASS_ChannelSetDevice(self.handle, bass.get_device_number()) == 0 {
+            return Err(BassError::last());
+        }
 
-        BASS_ChannelGetAttribute(self.handle, BASS_ATTRIB_BITRATE, &mut bit_rate as *mut f32);
+        self.bass = bass.clone();
 
-        bit_rate
+        Ok(())
     }
 
-    pub fn get_buffering_length(&self) -> f32 {
-        let mut buffering_length = 0.0f32;
+    pub fn info(&self) -> Result<ChannelInfo, BassError> {
+        let mut info = BassChannelInfo::new(0, 0, 0, 0, 0, 0, 0, ptr::null());
 
-        BASS_ChannelGetAttribute(
-            self.handle,
-            BASS_ATTRIB_BUFFER,
-            &mut buffering_length as *mut f32,
-        );
+        if BASS_ChannelGetInfo(self.handle, &mut info as *mut BassChannelInfo) == 0 {
+            return Err(BassError::last());
+        }
 
-        buffering_length
+        Ok(ChannelInfo::from_raw(&info))
     }
 
-    pub fn get_sample_rate(&self) -> f32 {
-        let mut sample_rate = 0.0f32;
+    /// Returns the device the stream is playing on, or `None` for decoding streams.
+    pub fn get_device(&self) -> Result<Option<Device>, BassError> {
+        let device = BASS_ChannelGetDevice(self.handle);
 
-        BASS_ChannelGetAttribute(self.handle, BASS_ATTRIB_FREQ, &mut sample_rate as *mut f32);
+        if device == DWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        if device == BASS_NODEVICE {
+            return Ok(None);
+        }
 
-        sample_rate
+        Device::get(device).map(Some)
     }
 
-    pub fn get_processing_granularity(&self) -> f32 {
-        let mut processing_granularity = 0.0f32;
+    pub fn get_bit_rate(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Bitrate)
+    }
 
-        BASS_ChannelGetAttribute(
-            self.handle,
-            BASS_ATTRIB_GRANULE,
-            &mut processing_granularity as *mut f32,
-        );
+    pub fn get_buffering_length(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Buffer)
+    }
 
-        processing_granularity
+    pub fn get_sample_rate(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Frequency)
     }
 
-    pub fn get_buffer_level_required_to_resume_stalled_playback(&self) -> f32 {
-        let mut buffer_level_required_to_resume_stalled_playback = 0.0f32;
+    pub fn get_processing_granularity(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Granule)
+    }
 
-        BASS_ChannelGetAttribute(
-            self.handle,
-            BASS_ATTRIB_NET_RESUME,
-            &mut buffer_level_required_to_resume_stalled_playback as *mut f32,
-        );
+    pub fn get_buffer_level_required_to_resume_stalled_playback(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::NetResume)
+    }
 
-        buffer_level_required_to_resume_stalled_playback
+    pub fn get_playback_buffering_switch(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::NoBuffer)
     }
 
-    pub fn get_playback_buffering_switch(&self) -> f32 {
-        let mut playback_buffering_switch = 0.0f32;
+    pub fn get_playback_ramping_switch(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::NoRamp)
+    }
 
-        BASS_ChannelGetAttribute(
-            self.handle,
-            BASS_ATTRIB_NOBUFFER,
-            &mut playback_buffering_switch as *mut f32,
-        );
+    pub fn get_panning_position(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Pan)
+    }
 
-        playback_buffering_switch
+    pub fn get_sample_rate_conversion_quality(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::SampleRateConversion)
     }
 
-    pub fn get_playback_ramping_switch(&self) -> f32 {
-        let mut playback_ramping_switch = 0.0f32;
+    pub fn get_volume(&self) -> Result<f32, BassError> {
+        self.attribute(Attribute::Volume)
+    }
 
-        BASS_ChannelGetAttribute(
-            self.handle,
-            BASS_ATTRIB_NORAMP,
-            &mut playback_ramping_switch as *mut f32,
-        );
+    pub fn set_buffering_length(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::Buffer, value)
+    }
 
-        playback_ramping_switch
+    pub fn set_sample_rate(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::Frequency, value)
     }
 
-    pub fn get_panning_position(&self) -> f32 {
-        let mut panning_position = 0.0f32;
+    pub fn set_processing_granularity(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::Granule, value)
+    }
+
+    pub fn set_buffer_level_required_to_resume_stalled_playback(
+        &self,
+        value: f32,
+    ) -> Result<(), BassError> {
+        self.set_attribute(Attribute::NetResume, value)
+    }
 
-        BASS_ChannelGetAttribute(
+    pub fn set_playback_buffering_switch(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::NoBuffer, value)
+    }
+
+    pub fn set_playback_ramping_switch(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::NoRamp, value)
+    }
+
+    pub fn set_panning_position(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::Pan, value)
+    }
+
+    pub fn set_volume(&self, value: f32) -> Result<(), BassError> {
+        self.set_attribute(Attribute::Volume, value)
+    }
+
+    pub fn attribute(&self, attribute: Attribute) -> Result<f32, BassError> {
+        let mut value = 0.0f32;
+
+        if BASS_ChannelGetAttribute(self.handle, attribute.raw(), &mut value as *mut f32) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(value)
+    }
+
+    pub fn set_attribute(&self, attribute: Attribute, value: f32) -> Result<(), BassError> {
+        validate_attribute(attribute, value)?;
+
+        if BASS_ChannelSetAttribute(self.handle, attribute.raw(), value) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(())
+    }
+
+    /// Reads a non floating-point attribute into `buffer` and returns its size in bytes, an empty buffer only queries the size.
+    pub fn attribute_ex(
+        &self,
+        attribute: Attribute,
+        buffer: &mut [u8],
+    ) -> Result<usize, BassError> {
+        let buffer_raw = if buffer.is_empty() {
+            ptr::null_mut()
+        } else {
+            buffer.as_mut_ptr() as *mut c_void
+        };
+
+        let size = BASS_ChannelGetAttributeEx(
             self.handle,
-            BASS_ATTRIB_PAN,
-            &mut panning_position as *mut f32,
+            attribute.raw(),
+            buffer_raw,
+            buffer.len() as DWORD,
         );
 
-        panning_position
+        if size == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(size as usize)
+    }
+
+    pub fn set_attribute_ex(&self, attribute: Attribute, value: &[u8]) -> Result<(), BassError> {
+        if BASS_ChannelSetAttributeEx(
+            self.handle,
+            attribute.raw(),
+            value.as_ptr() as *mut c_void,
+            value.len() as DWORD,
+        ) == 0
+        {
+            return Err(BassError::last());
+        }
+
+        Ok(())
+    }
+
+    /// Slides the attribute from its current value to `value` over `duration`.
+    ///
+    /// Sliding the volume to `-1.0` stops the stream once the slide ends.
+    pub fn slide_attribute(
+        &self,
+        attribute: Attribute,
+        value: f32,
+        duration: Duration,
+    ) -> Result<(), BassError> {
+        self.slide_attribute_raw(attribute.raw(), attribute, value, duration)
     }
 
-    pub fn get_sample_rate_conversion_quality(&self) -> f32 {
-        let mut sample_rate_conversion_quality = 0.0f32;
+    /// Same as `slide_attribute`, but the slide follows a logarithmic curve.
+    pub fn slide_attribute_logarithmic(
+        &self,
+        attribute: Attribute,
+        value: f32,
+        duration: Duration,
+    ) -> Result<(), BassError> {
+        self.slide_attribute_raw(attribute.raw() | BASS_SLIDE_LOG, attribute, value, duration)
+    }
 
-        BASS_ChannelGetAttribute(
+    pub fn is_sliding(&self, attribute: Attribute) -> Result<bool, BassError> {
+        if BASS_ChannelIsSliding(self.handle, attribute.raw()) != 0 {
+            return Ok(true);
+        }
+
+        // Not sliding is also returned on failure, the error code tells them apart.
+        match BASS_ErrorGetCode() {
+            BASS_OK => Ok(false),
+            code => Err(BassError::from_code(code)),
+        }
+    }
+
+    /// Calls `callback` on a BASS thread every time a slide of the attribute ends, until the returned handle is dropped.
+    pub fn on_slide_end<F>(
+        &self,
+        attribute: Attribute,
+        mut callback: F,
+    ) -> Result<SyncHandle, BassError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let attribute = attribute.raw();
+
+        SyncHandle::new(
             self.handle,
-            BASS_ATTRIB_SRC,
-            &mut sample_rate_conversion_quality as *mut f32,
-        );
+            BASS_SYNC_SLIDE,
+            0,
+            Box::new(move |data| {
+                if data == attribute {
+                    callback();
+                }
+            }),
+        )
+    }
+
+    /// Calls `callback` whenever the sync is triggered, until the returned handle is dropped.
+    ///
+    /// The callback runs on a BASS thread, or in the mix itself for mixtime syncs, so it should return quickly.
+    pub fn on_sync<F>(&self, kind: SyncKind, callback: F) -> Result<SyncHandle, BassError>
+    where
+        F: FnMut(SyncEvent) + Send + 'static,
+    {
+        self.on_sync_with_flags(kind, SyncFlags::default(), callback)
+    }
+
+    pub fn on_sync_with_flags<F>(
+        &self,
+        kind: SyncKind,
+        flags: SyncFlags,
+        mut callback: F,
+    ) -> Result<SyncHandle, BassError>
+    where
+        F: FnMut(SyncEvent) + Send + 'static,
+    {
+        SyncHandle::new(
+            self.handle,
+            kind.raw() | flags.raw(),
+            kind.parameter(),
+            Box::new(move |data| callback(kind.event(data))),
+        )
+    }
+
+    /// Returns a receiver of the stream's playback events, the receiver returned before stops receiving them.
+    pub fn events(&self) -> Result<Receiver<PlaybackEvent>, BassError> {
+        let (events, receiver) = EventSyncs::new(self.handle)?;
+
+        *self
+            .events
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = Some(events);
+
+        Ok(receiver)
+    }
+
+    /// Sends `PlaybackEvent::PositionReached` when the position in bytes is reached, `events` has to be called first.
+    pub fn add_event_position(&self, position: u64) -> Result<(), BassError> {
+        let mut events = self
+            .events
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        match events.as_mut() {
+            Some(events) => events.add(SyncKind::Position(position)),
+            None => Err(BassError::NotAvailable),
+        }
+    }
+
+    /// Resolves when the end of the stream is reached, or right away if the stream is stopped.
+    #[cfg(feature = "async")]
+    pub fn finished(&self) -> SyncFuture {
+        let mut future = SyncFuture::new(self.handle, BASS_SYNC_END, 0);
+
+        if let Ok(ChannelState::Stopped) = self.state() {
+            future.resolve();
+        }
+
+        future
+    }
+
+    /// Resolves when the position in bytes is reached.
+    #[cfg(feature = "async")]
+    pub fn position_reached(&self, position: u64) -> SyncFuture {
+        SyncFuture::new(self.handle, BASS_SYNC_POS, position)
+    }
 
-        sample_rate_conversion_quality
+    /// Resolves when the attribute stops sliding, or right away if it isn't sliding.
+    #[cfg(feature = "async")]
+    pub fn slide_finished(&self, attribute: Attribute) -> SyncFuture {
+        let raw_attribute = attribute.raw();
+
+        let mut future = SyncFuture::with_filter(self.handle, BASS_SYNC_SLIDE, 0, move |data| {
+            data == raw_attribute
+        });
+
+        if let Ok(false) = self.is_sliding(attribute) {
+            future.resolve();
+        }
+
+        future
     }
 
-    pub fn get_volume(&self) -> f32 {
-        let mut volume = 0.0f32;
+    /// Resolves when the file of an internet stream has been downloaded completely.
+    #[cfg(feature = "async")]
+    pub fn download_finished(&self) -> SyncFuture {
+        SyncFuture::new(self.handle, BASS_SYNC_DOWNLOAD, 0)
+    }
 
-        BASS_ChannelGetAttribute(self.handle, BASS_ATTRIB_VOL, &mut volume as *mut f32);
+    /// Processes the stream's samples with `callback`, until the returned handle is dropped.
+    ///
+    /// `T` has to be `f32` for floating-point channels or when `Config::set_float_dsp` is enabled, otherwise it's the channel's sample type. DSPs with a higher priority are applied first.
+    pub fn add_dsp<T, F>(&self, priority: i32, callback: F) -> Result<DspHandle, BassError>
+    where
+        T: Sample,
+        F: FnMut(&mut [T]) + Send + 'static,
+    {
+        let format = self.info()?.get_sample_format();
+
+        DspHandle::new(self.handle, format, priority, callback)
+    }
 
-        volume
+    /// Applies one of the built-in processors, or any other `Processor`, until the returned handle is dropped.
+    pub fn add_processor<P>(
+        &self,
+        priority: i32,
+        processor: P,
+    ) -> Result<ProcessorHandle<P>, BassError>
+    where
+        P: Processor,
+    {
+        ProcessorHandle::new(self.handle, &self.info()?, priority, processor)
     }
 
-    pub fn set_buffering_length(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_BUFFER, value);
+    fn slide_attribute_raw(
+        &self,
+        raw_attribute: DWORD,
+        attribute: Attribute,
+        value: f32,
+        duration: Duration,
+    ) -> Result<(), BassError> {
+        let stops_stream = attribute == Attribute::Volume && value == -1.0;
+
+        if !stops_stream {
+            validate_attribute(attribute, value)?;
+        }
+
+        if duration.as_millis() > DWORD::MAX as u128 {
+            return Err(BassError::ValueOutOfRange);
+        }
+
+        if BASS_ChannelSlideAttribute(
+            self.handle,
+            raw_attribute,
+            value,
+            duration.as_millis() as DWORD,
+        ) == 0
+        {
+            return Err(BassError::last());
+        }
+
+        Ok(())
     }
 
-    pub fn set_sample_rate(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_FREQ, value);
+    pub fn seek(&self, position: Position) -> Result<(), BassError> {
+        self.seek_with_flags(position, PositionFlags::default())
     }
 
-    pub fn set_processing_granularity(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_GRANULE, value);
+    pub fn seek_with_flags(
+        &self,
+        position: Position,
+        flags: PositionFlags,
+    ) -> Result<(), BassError> {
+        let (position, mode) = match position {
+            Position::Bytes(bytes) => (bytes, BASS_POS_BYTE),
+            Position::Time(time) => (self.duration_to_bytes(time)?, BASS_POS_BYTE),
+            Position::MusicOrder { order, row } => {
+                (order as QWORD | (row as QWORD) << 16, BASS_POS_MUSIC_ORDER)
+            }
+            Position::OggBitstream(bitstream) => (bitstream as QWORD, BASS_POS_OGG),
+        };
+
+        if BASS_ChannelSetPosition(self.handle, position, mode | flags.raw()) == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(())
     }
 
-    pub fn set_buffer_level_required_to_resume_stalled_playback(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_NET_RESUME, value);
+    pub fn position(&self, mode: PositionMode, flags: PositionFlags) -> Result<u64, BassError> {
+        let position = BASS_ChannelGetPosition(self.handle, mode.raw() | flags.raw());
+
+        if position == QWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(position)
     }
 
-    pub fn set_playback_buffering_switch(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_NOBUFFER, value);
+    pub fn length(&self, mode: PositionMode) -> Result<u64, BassError> {
+        let length = BASS_ChannelGetLength(self.handle, mode.raw());
+
+        if length == QWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(length)
     }
 
-    pub fn set_playback_ramping_switch(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_NORAMP, value);
+    pub fn duration(&self) -> Result<Duration, BassError> {
+        self.bytes_to_duration(self.length(PositionMode::Bytes)?)
     }
 
-    pub fn set_panning_position(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_PAN, value);
+    pub fn bytes_to_duration(&self, bytes: u64) -> Result<Duration, BassError> {
+        let seconds = BASS_ChannelBytes2Seconds(self.handle, bytes);
+
+        if seconds < 0.0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Duration::from_secs_f64(seconds))
     }
 
-    pub fn set_volume(&self, value: f32) {
-        BASS_ChannelSetAttribute(self.handle, BASS_ATTRIB_VOL, value);
+    pub fn duration_to_bytes(&self, duration: Duration) -> Result<u64, BassError> {
+        let bytes = BASS_ChannelSeconds2Bytes(self.handle, duration.as_secs_f64());
+
+        if bytes == QWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(bytes)
     }
 
-    pub fn get_position(&self) -> u64 {
-        BASS_ChannelGetPosition(self.handle, 0)
+    pub fn get_position(&self) -> Result<u64, BassError> {
+        let position = BASS_ChannelGetPosition(self.handle, BASS_POS_BYTE);
+
+        if position == QWORD::MAX {
+            return Err(BassError::last());
+        }
+
+        Ok(position)
     }
 
-    pub fn get_time(&self) -> f64 {
-        BASS_ChannelBytes2Seconds(self.handle, self.get_position())
+    pub fn get_time(&self) -> Result<f64, BassError> {
+        let seconds = BASS_ChannelBytes2Seconds(self.handle, self.get_position()?);
+
+        if seconds < 0.0 {
+            return Err(BassError::last());
+        }
+
+        Ok(seconds)
     }
 
     pub fn get_raw_handle(&self) -> &HSTREAM {
         &self.handle
     }
 }
+
+fn validate_attribute(attribute: Attribute, value: f32) -> Result<(), BassError> {
+    let is_valid = match attribute {
+        Attribute::Pan => (-1.0..=1.0).contains(&value),
+        Attribute::Volume => value >= 0.0,
+        _ => true,
+    };
+
+    if !is_valid {
+        return Err(BassError::ValueOutOfRange);
+    }
+
+    Ok(())
+}
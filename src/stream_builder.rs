@@ -0,0 +1,401 @@
+use crate::reader::file_procs;
+use crate::stream_proc::stream_proc;
+use crate::user_data::UserData;
+use crate::{
+    Bass, BassError, FileSystem, PushFileStream, PushStream, Sample, SampleFormat, Stream,
+    StreamStatus,
+};
+
+#[cfg(target_family = "unix")]
+use std::ffi::CString;
+
+use std::any::Any;
+use std::ffi::c_void;
+use std::io::{Read, Seek};
+use std::os::raw::c_char;
+use std::ptr;
+
+use bass_sys::*;
+
+#[cfg(target_os = "windows")]
+use widestring::U16CString;
+
+// The special `STREAMPROC` values aren't exported by bass-sys.
+const STREAMPROC_DUMMY: *mut STREAMPROC = ptr::null_mut();
+const STREAMPROC_PUSH: *mut STREAMPROC = -1isize as *mut STREAMPROC;
+const STREAMPROC_DEVICE: *mut STREAMPROC = -2isize as *mut STREAMPROC;
+const STREAMPROC_DEVICE_3D: *mut STREAMPROC = -3isize as *mut STREAMPROC;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeakerAssignment {
+    Front,
+    Rear,
+    CenterLfe,
+    Rear2,
+    FrontLeft,
+    FrontRight,
+    RearLeft,
+    RearRight,
+    Center,
+    Lfe,
+    Rear2Left,
+    Rear2Right,
+    /// The n-th pair of speakers, from `1` to `15`.
+    Pair(u8),
+}
+
+impl SpeakerAssignment {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            SpeakerAssignment::Front => BASS_SPEAKER_FRONT,
+            SpeakerAssignment::Rear => BASS_SPEAKER_REAR,
+            SpeakerAssignment::CenterLfe => BASS_SPEAKER_CENLFE,
+            SpeakerAssignment::Rear2 => BASS_SPEAKER_REAR2,
+            SpeakerAssignment::FrontLeft => BASS_SPEAKER_FRONTLEFT,
+            SpeakerAssignment::FrontRight => BASS_SPEAKER_FRONTRIGHT,
+            SpeakerAssignment::RearLeft => BASS_SPEAKER_REARLEFT,
+            SpeakerAssignment::RearRight => BASS_SPEAKER_REARRIGHT,
+            SpeakerAssignment::Center => BASS_SPEAKER_CENTER,
+            SpeakerAssignment::Lfe => BASS_SPEAKER_LFE,
+            SpeakerAssignment::Rear2Left => BASS_SPEAKER_REAR2LEFT,
+            SpeakerAssignment::Rear2Right => BASS_SPEAKER_REAR2RIGHT,
+            SpeakerAssignment::Pair(pair) => ((*pair as DWORD) & 0xF) << 24,
+        }
+    }
+}
+
+/// Builds a `Stream` with the `BASS_SAMPLE_*` and `BASS_STREAM_*` flags.
+#[derive(Clone)]
+pub struct StreamBuilder {
+    bass: Bass,
+    flags: DWORD,
+    offset: u64,
+    length: u64,
+}
+
+impl StreamBuilder {
+    pub fn new(bass: &Bass) -> StreamBuilder {
+        StreamBuilder {
+            bass: bass.clone(),
+            flags: 0,
+            offset: 0,
+            length: 0,
+        }
+    }
+
+    pub fn sample_format(mut self, format: SampleFormat) -> StreamBuilder {
+        self.flags &= !(BASS_SAMPLE_FLOAT | BASS_SAMPLE_8BITS);
+        self.flags |= format.raw();
+        self
+    }
+
+    pub fn mono(mut self) -> StreamBuilder {
+        self.flags |= BASS_SAMPLE_MONO;
+        self
+    }
+
+    pub fn looping(mut self) -> StreamBuilder {
+        self.flags |= BASS_SAMPLE_LOOP;
+        self
+    }
+
+    pub fn three_dimensional(mut self) -> StreamBuilder {
+        self.flags |= BASS_SAMPLE_3D;
+        self
+    }
+
+    pub fn prescan(mut self) -> StreamBuilder {
+        self.flags |= BASS_STREAM_PRESCAN;
+        self
+    }
+
+    /// Makes BASS free the stream as soon as it stops or ends, the `Stream` is left with an invalid handle then.
+    pub fn auto_free(mut self) -> StreamBuilder {
+        self.flags |= BASS_STREAM_AUTOFREE;
+        self
+    }
+
+    /// Makes the stream a decoding channel, it can't be played but its data can be read.
+    pub fn decode_only(mut self) -> StreamBuilder {
+        self.flags |= BASS_STREAM_DECODE;
+        self
+    }
+
+    pub fn speaker_assignment(mut self, speakers: SpeakerAssignment) -> StreamBuilder {
+        self.flags &= !0xFF000000;
+        self.flags |= speakers.raw();
+        self
+    }
+
+    /// The offset in the file or memory to start streaming from, in bytes.
+    pub fn offset(mut self, offset: u64) -> StreamBuilder {
+        self.offset = offset;
+        self
+    }
+
+    /// The length of the data to stream, in bytes, `0` streams until the end of the file or memory.
+    pub fn length(mut self, length: u64) -> StreamBuilder {
+        self.length = length;
+        self
+    }
+
+    pub fn create_from_file(self, file_name: String) -> Result<Stream, BassError> {
+        self.bass.make_current()?;
+
+        let handle;
+
+        #[cfg(target_family = "windows")]
+        {
+            let file_name_raw =
+                U16CString::from_str(file_name).map_err(|_| BassError::IllegalParameter)?;
+            let file_name_raw = file_name_raw.as_ptr() as *const c_void;
+
+            handle = BASS_StreamCreateFile(
+                0,
+                file_name_raw,
+                self.offset,
+                self.length,
+                self.flags | BASS_UNICODE,
+            );
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            let file_name_raw = CString::new(file_name).map_err(|_| BassError::IllegalParameter)?;
+            let file_name_raw = file_name_raw.as_ptr() as *const c_void;
+
+            handle = BASS_StreamCreateFile(0, file_name_raw, self.offset, self.length, self.flags);
+        }
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Stream::from_raw(handle, self.bass))
+    }
+
+    /// Creates a stream from an internet file, the length is ignored.
+    pub fn create_from_url(self, url: String) -> Result<Stream, BassError> {
+        if self.offset > DWORD::MAX as u64 {
+            return Err(BassError::ValueOutOfRange);
+        }
+
+        self.bass.make_current()?;
+
+        let handle;
+
+        #[cfg(target_family = "windows")]
+        {
+            let url_raw = U16CString::from_str(url).map_err(|_| BassError::IllegalParameter)?;
+            let url_raw = url_raw.as_ptr() as *const c_char;
+
+            handle = BASS_StreamCreateURL(
+                url_raw,
+                self.offset as DWORD,
+                self.flags | BASS_UNICODE,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            let url_raw = CString::new(url).map_err(|_| BassError::IllegalParameter)?;
+            let url_raw = url_raw.as_ptr() as *const c_char;
+
+            handle = BASS_StreamCreateURL(
+                url_raw,
+                self.offset as DWORD,
+                self.flags,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+        }
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Stream::from_raw(handle, self.bass))
+    }
+
+    pub fn create_from_memory(self, data: &'static [u8]) -> Result<Stream, BassError> {
+        let handle = self.create_from_memory_raw(data)?;
+
+        Ok(Stream::from_raw(handle, self.bass))
+    }
+
+    /// Creates a stream from a buffer, which is kept alive as long as the stream.
+    pub fn create_from_owned_memory<T>(self, data: T) -> Result<Stream, BassError>
+    where
+        T: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        // Boxing first, so the data doesn't move once BASS has a pointer to it.
+        let data = Box::new(data);
+
+        let handle = self.create_from_memory_raw((*data).as_ref())?;
+
+        Ok(Stream::from_raw_with_data(
+            handle,
+            self.bass,
+            data as Box<dyn Any + Send + Sync>,
+        ))
+    }
+
+    fn create_from_memory_raw(&self, data: &[u8]) -> Result<HSTREAM, BassError> {
+        let data = data
+            .get(self.offset as usize..)
+            .ok_or(BassError::ValueOutOfRange)?;
+
+        let data = match self.length {
+            0 => data,
+            length => data
+                .get(..length as usize)
+                .ok_or(BassError::ValueOutOfRange)?,
+        };
+
+        self.bass.make_current()?;
+
+        let handle = BASS_StreamCreateFile(
+            1,
+            data.as_ptr() as *const c_void,
+            0,
+            data.len() as QWORD,
+            self.flags,
+        );
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(handle)
+    }
+
+    /// Creates a stream reading from `reader`, which is dropped when the stream is, the offset and length are ignored.
+    pub fn create_from_reader<R>(self, reader: R, system: FileSystem) -> Result<Stream, BassError>
+    where
+        R: Read + Seek + Send + 'static,
+    {
+        let reader = UserData::new(reader);
+        let mut procs = file_procs::<R>();
+
+        self.bass.make_current()?;
+
+        let handle = BASS_StreamCreateFileUser(
+            system.raw(),
+            self.flags,
+            &mut procs as *mut BassFileProcs,
+            reader.as_raw(),
+        );
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Stream::from_raw_with_data(
+            handle,
+            self.bass,
+            Box::new(reader),
+        ))
+    }
+
+    /// Creates a stream pulling its samples from `callback`, which is called on a BASS thread.
+    ///
+    /// The sample format is the one of `T`, the offset and length are ignored.
+    pub fn create_with_callback<T, F>(
+        self,
+        frequency: u32,
+        channels: u32,
+        callback: F,
+    ) -> Result<Stream, BassError>
+    where
+        T: Sample,
+        F: FnMut(&mut [T]) -> StreamStatus + Send + 'static,
+    {
+        let builder = self.sample_format(T::FORMAT);
+        let callback = UserData::new(callback);
+
+        builder.bass.make_current()?;
+
+        let handle = BASS_StreamCreate(
+            frequency,
+            channels,
+            builder.flags,
+            stream_proc::<T, F> as *mut STREAMPROC,
+            callback.as_raw(),
+        );
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(Stream::from_raw_with_data(
+            handle,
+            builder.bass,
+            Box::new(callback),
+        ))
+    }
+
+    /// Creates a stream playing the samples pushed to it, the sample format is the one of `T`.
+    pub fn create_push_stream<T: Sample>(
+        self,
+        frequency: u32,
+        channels: u32,
+    ) -> Result<PushStream<T>, BassError> {
+        let builder = self.sample_format(T::FORMAT);
+
+        let handle = builder.create_with_special_proc(frequency, channels, STREAMPROC_PUSH)?;
+
+        let stream = Stream::from_raw(handle, builder.bass);
+
+        Ok(PushStream::from_stream(stream))
+    }
+
+    /// Creates a buffered user file stream whose file data is pushed to it, `reader` only provides the data required to create it.
+    pub fn create_push_file_stream<R>(self, reader: R) -> Result<PushFileStream, BassError>
+    where
+        R: Read + Send + 'static,
+    {
+        PushFileStream::from_reader(self, reader)
+    }
+
+    /// Creates a stream without any data of its own, usually combined with `decode_only` to process data with DSP and effects.
+    pub fn create_dummy(self, frequency: u32, channels: u32) -> Result<Stream, BassError> {
+        let handle = self.create_with_special_proc(frequency, channels, STREAMPROC_DUMMY)?;
+
+        Ok(Stream::from_raw(handle, self.bass))
+    }
+
+    /// Returns the final output mix of the device as a stream, to set DSP and effects on it, the builder options are ignored.
+    ///
+    /// BASS hands out the same stream every time and frees it itself in `BASS_Free`, so dropping the returned stream doesn't free it.
+    pub fn create_device_output(self) -> Result<Stream, BassError> {
+        let handle = self.create_with_special_proc(0, 0, STREAMPROC_DEVICE)?;
+
+        Ok(Stream::from_raw_shared(handle, self.bass))
+    }
+
+    /// Same as `create_device_output`, but for the 3D output mix.
+    pub fn create_device_output_3d(self) -> Result<Stream, BassError> {
+        let handle = self.create_with_special_proc(0, 0, STREAMPROC_DEVICE_3D)?;
+
+        Ok(Stream::from_raw_shared(handle, self.bass))
+    }
+
+    fn create_with_special_proc(
+        &self,
+        frequency: u32,
+        channels: u32,
+        proc: *mut STREAMPROC,
+    ) -> Result<HSTREAM, BassError> {
+        self.bass.make_current()?;
+
+        let handle = BASS_StreamCreate(frequency, channels, self.flags, proc, ptr::null_mut());
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(handle)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Sample;
+
+use std::ffi::c_void;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use bass_sys::*;
+
+/// What a stream callback returns after writing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// The whole buffer was filled.
+    Continue,
+    /// Only the given number of samples was written and the stream has ended.
+    End(usize),
+}
+
+pub(crate) extern "system" fn stream_proc<T, F>(
+    _handle: HSTREAM,
+    buffer: *mut c_void,
+    length: DWORD,
+    user: *mut c_void,
+) -> DWORD
+where
+    T: Sample,
+    F: FnMut(&mut [T]) -> StreamStatus,
+{
+    let callback = unsafe { &mut *(user as *mut F) };
+    let samples = unsafe {
+        slice::from_raw_parts_mut(buffer as *mut T, length as usize / mem::size_of::<T>())
+    };
+    let sample_count = samples.len();
+
+    // Unwinding into BASS is undefined behaviour, a panicking callback ends the stream instead.
+    match panic::catch_unwind(AssertUnwindSafe(|| callback(samples))) {
+        Ok(StreamStatus::Continue) => length - length % mem::size_of::<T>() as DWORD,
+        Ok(StreamStatus::End(written)) => {
+            (written.min(sample_count) * mem::size_of::<T>()) as DWORD | BASS_STREAMPROC_END
+        }
+        Err(_) => BASS_STREAMPROC_END,
+    }
+}
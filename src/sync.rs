@@ -0,0 +1,165 @@
+use crate::user_data::UserData;
+use crate::BassError;
+
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+
+use bass_sys::*;
+
+type SyncCallback = Box<dyn FnMut(DWORD) + Send>;
+
+/// What a sync is triggered by, see the `BASS_SYNC_*` types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncKind {
+    End,
+    /// The position in bytes is reached.
+    Position(u64),
+    Meta,
+    OggChange,
+    /// Playback stalls or resumes.
+    Stall,
+    Download,
+    Free,
+    SetPosition,
+    DeviceFail,
+    DeviceFormat,
+}
+
+impl SyncKind {
+    pub fn raw(&self) -> DWORD {
+        match self {
+            SyncKind::End => BASS_SYNC_END,
+            SyncKind::Position(_) => BASS_SYNC_POS,
+            SyncKind::Meta => BASS_SYNC_META,
+            SyncKind::OggChange => BASS_SYNC_OGG_CHANGE,
+            SyncKind::Stall => BASS_SYNC_STALL,
+            SyncKind::Download => BASS_SYNC_DOWNLOAD,
+            SyncKind::Free => BASS_SYNC_FREE,
+            SyncKind::SetPosition => BASS_SYNC_SETPOS,
+            SyncKind::DeviceFail => BASS_SYNC_DEV_FAIL,
+            SyncKind::DeviceFormat => BASS_SYNC_DEV_FORMAT,
+        }
+    }
+
+    pub(crate) fn parameter(&self) -> QWORD {
+        match self {
+            SyncKind::Position(position) => *position,
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn event(&self, data: DWORD) -> SyncEvent {
+        match self {
+            SyncKind::End => SyncEvent::Ended,
+            SyncKind::Position(_) => SyncEvent::PositionReached,
+            SyncKind::Meta => SyncEvent::MetadataChanged,
+            SyncKind::OggChange => SyncEvent::OggBitstreamChanged,
+            SyncKind::Stall if data == 0 => SyncEvent::Stalled,
+            SyncKind::Stall => SyncEvent::Resumed,
+            SyncKind::Download => SyncEvent::DownloadCompleted,
+            SyncKind::Free => SyncEvent::Freed,
+            SyncKind::SetPosition => SyncEvent::PositionSet { flushed: data != 0 },
+            SyncKind::DeviceFail => SyncEvent::DeviceFailed,
+            SyncKind::DeviceFormat => SyncEvent::DeviceFormatChanged,
+        }
+    }
+}
+
+/// What triggered a sync, passed to the callback of `Stream::on_sync`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncEvent {
+    Ended,
+    PositionReached,
+    MetadataChanged,
+    OggBitstreamChanged,
+    Stalled,
+    Resumed,
+    DownloadCompleted,
+    Freed,
+    /// The position was changed, `flushed` tells if the playback buffer was flushed.
+    PositionSet {
+        flushed: bool,
+    },
+    DeviceFailed,
+    DeviceFormatChanged,
+}
+
+/// Modifiers of syncs, see the `BASS_SYNC_*` flags.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncFlags {
+    /// Calls the sync as soon as it's triggered in the mix, instead of when it's heard.
+    pub mixtime: bool,
+    /// Removes the sync after it's been called once.
+    pub onetime: bool,
+    /// Calls a mixtime sync on another thread, so it can't delay the mix.
+    pub thread: bool,
+}
+
+impl SyncFlags {
+    pub fn raw(&self) -> DWORD {
+        let mut flags = 0;
+
+        if self.mixtime {
+            flags |= BASS_SYNC_MIXTIME;
+        }
+
+        if self.onetime {
+            flags |= BASS_SYNC_ONETIME;
+        }
+
+        if self.thread {
+            flags |= BASS_SYNC_THREAD;
+        }
+
+        flags
+    }
+}
+
+/// A sync set on a channel, it's removed when dropped.
+pub struct SyncHandle {
+    channel: DWORD,
+    handle: HSYNC,
+    _callback: UserData<SyncCallback>,
+}
+
+impl Drop for SyncHandle {
+    fn drop(&mut self) {
+        BASS_ChannelRemoveSync(self.channel, self.handle);
+    }
+}
+
+impl SyncHandle {
+    pub(crate) fn new(
+        channel: DWORD,
+        sync_type: DWORD,
+        parameter: QWORD,
+        callback: SyncCallback,
+    ) -> Result<SyncHandle, BassError> {
+        let callback = UserData::new(callback);
+
+        let handle = BASS_ChannelSetSync(
+            channel,
+            sync_type,
+            parameter,
+            sync_proc as *mut SYNCPROC,
+            callback.as_raw(),
+        );
+
+        if handle == 0 {
+            return Err(BassError::last());
+        }
+
+        Ok(SyncHandle {
+            channel,
+            handle,
+            _callback: callback,
+        })
+    }
+}
+
+extern "system" fn sync_proc(_handle: HSYNC, _channel: DWORD, data: DWORD, user: *mut c_void) {
+    let callback = unsafe { &mut *(user as *mut SyncCallback) };
+
+    // Unwinding into BASS is undefined behaviour, so the panic is dropped here.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(data)));
+}
@@ -0,0 +1,29 @@
+use std::ffi::c_void;
+
+/// Owns a value that is handed to BASS as the `user` pointer of a callback.
+///
+/// The value is only ever accessed by the callback, so it's fine to share the owner between threads.
+pub(crate) struct UserData<T> {
+    raw: *mut T,
+}
+
+unsafe impl<T: Send> Send for UserData<T> {}
+unsafe impl<T: Send> Sync for UserData<T> {}
+
+impl<T> UserData<T> {
+    pub(crate) fn new(value: T) -> UserData<T> {
+        UserData {
+            raw: Box::into_raw(Box::new(value)),
+        }
+    }
+
+    pub(crate) fn as_raw(&self) -> *mut c_void {
+        self.raw as *mut c_void
+    }
+}
+
+impl<T> Drop for UserData<T> {
+    fn drop(&mut self) {
+        unsafe { drop(Box::from_raw(self.raw)) };
+    }
+}
@@ -0,0 +1,395 @@
+use crate::sample::f32_to_i16;
+use crate::{Bass, DecodeStream, SampleFormat, StreamBuilder};
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+// The tail of the KSDATAFORMAT_SUBTYPE_* GUIDs, the format tag goes in front of it.
+const SUBFORMAT_GUID_TAIL: [u8; 14] = [
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+
+// The size of the "ds64" chunk without a table, it's reserved with a "JUNK" chunk until the size is known.
+const DS64_SIZE: u32 = 28;
+
+const BUFFER_LENGTH: usize = 65536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavFormat {
+    Float,
+    Int16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavOptions {
+    pub format: WavFormat,
+    /// Always uses `WAVE_FORMAT_EXTENSIBLE`, it's used anyway for more than two channels.
+    pub extensible: bool,
+}
+
+impl Default for WavOptions {
+    fn default() -> WavOptions {
+        WavOptions {
+            format: WavFormat::Int16,
+            extensible: false,
+        }
+    }
+}
+
+pub enum RenderSource {
+    File(String),
+    Url(String),
+    Memory(Arc<[u8]>),
+}
+
+/// Decodes the whole source and writes it to a WAV file, switching to RF64 when it doesn't fit in 4 GB.
+///
+/// The source is decoded without playing it, so `bass` can be the "no sound" device. Returns the number of bytes of audio data written.
+pub fn render_to_wav<P: AsRef<Path>>(
+    bass: &Bass,
+    source: RenderSource,
+    path: P,
+    options: &WavOptions,
+) -> io::Result<u64> {
+    let format = match options.format {
+        WavFormat::Float => SampleFormat::Float,
+        WavFormat::Int16 => SampleFormat::Int16,
+    };
+
+    let builder = StreamBuilder::new(bass).sample_format(format).decode_only();
+
+    let stream = match source {
+        RenderSource::File(file_name) => builder.create_from_file(file_name)?,
+        RenderSource::Url(url) => builder.create_from_url(url)?,
+        RenderSource::Memory(data) => builder.create_from_owned_memory(data)?,
+    };
+
+    let mut stream = DecodeStream::try_from(stream)?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    let length = write_wav(&mut stream, &mut writer, options)?;
+
+    writer.flush()?;
+
+    Ok(length)
+}
+
+/// Writes the rest of the stream as a WAV file, see `render_to_wav`.
+pub fn write_wav<W: Write + Seek>(
+    stream: &mut DecodeStream,
+    mut writer: W,
+    options: &WavOptions,
+) -> io::Result<u64> {
+    let channels = stream.get_channels() as u16;
+    let frequency = stream.get_frequency();
+
+    // The header is written again once the length is known, it keeps the same size.
+    let start = writer.stream_position()?;
+
+    write_wav_header(&mut writer, channels, frequency, options, 0)?;
+
+    let data_length = write_samples(stream, &mut writer, options.format)?;
+    let end = writer.stream_position()?;
+
+    writer.seek(SeekFrom::Start(start))?;
+
+    write_wav_header(&mut writer, channels, frequency, options, data_length)?;
+
+    writer.seek(SeekFrom::Start(end))?;
+
+    Ok(data_length)
+}
+
+/// Writes the header of a WAV file followed by `data_length` bytes of audio data, as RF64 if it doesn't fit in 4 GB.
+///
+/// The "ds64" chunk is written as a "JUNK" chunk for smaller files, so the header always has the same size.
+fn write_wav_header<W: Write>(
+    mut writer: W,
+    channels: u16,
+    frequency: u32,
+    options: &WavOptions,
+    data_length: u64,
+) -> io::Result<()> {
+    let (format_tag, bits_per_sample) = match options.format {
+        WavFormat::Float => (WAVE_FORMAT_IEEE_FLOAT, 32u16),
+        WavFormat::Int16 => (WAVE_FORMAT_PCM, 16u16),
+    };
+
+    let is_extensible = options.extensible || channels > 2;
+    let has_fact = format_tag != WAVE_FORMAT_PCM;
+    let block_align = channels * bits_per_sample / 8;
+    let sample_count = data_length / block_align.max(1) as u64;
+
+    let fmt_size = if is_extensible {
+        40u32
+    } else if has_fact {
+        18u32
+    } else {
+        16u32
+    };
+
+    let header_length = 12 + (8 + DS64_SIZE) + (8 + fmt_size) + if has_fact { 12 } else { 0 } + 8;
+    let riff_size = header_length as u64 - 8 + data_length;
+    let is_rf64 = riff_size > u32::MAX as u64;
+
+    if is_rf64 {
+        writer.write_all(b"RF64")?;
+        writer.write_all(&u32::MAX.to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"ds64")?;
+        writer.write_all(&DS64_SIZE.to_le_bytes())?;
+        writer.write_all(&riff_size.to_le_bytes())?;
+        writer.write_all(&data_length.to_le_bytes())?;
+        writer.write_all(&sample_count.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+    } else {
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(riff_size as u32).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"JUNK")?;
+        writer.write_all(&DS64_SIZE.to_le_bytes())?;
+        writer.write_all(&[0u8; DS64_SIZE as usize])?;
+    }
+
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&fmt_size.to_le_bytes())?;
+    writer.write_all(
+        &(if is_extensible {
+            WAVE_FORMAT_EXTENSIBLE
+        } else {
+            format_tag
+        })
+        .to_le_bytes(),
+    )?;
+    writer.write_all(&channels.to_le_bytes())?;
+    writer.write_all(&frequency.to_le_bytes())?;
+    writer.write_all(&(frequency * block_align as u32).to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&bits_per_sample.to_le_bytes())?;
+
+    if is_extensible {
+        writer.write_all(&22u16.to_le_bytes())?;
+        writer.write_all(&bits_per_sample.to_le_bytes())?;
+        writer.write_all(&channel_mask(channels).to_le_bytes())?;
+        writer.write_all(&(format_tag as u32).to_le_bytes())?;
+        writer.write_all(&SUBFORMAT_GUID_TAIL)?;
+    } else if has_fact {
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+
+    if has_fact {
+        let fact_count = if is_rf64 {
+            u32::MAX
+        } else {
+            sample_count.min(u32::MAX as u64) as u32
+        };
+
+        writer.write_all(b"fact")?;
+        writer.write_all(&4u32.to_le_bytes())?;
+        writer.write_all(&fact_count.to_le_bytes())?;
+    }
+
+    let data_size = if is_rf64 {
+        u32::MAX
+    } else {
+        data_length as u32
+    };
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_size.to_le_bytes())?;
+
+    Ok(())
+}
+
+fn write_samples<W: Write>(
+    stream: &mut DecodeStream,
+    writer: &mut W,
+    format: WavFormat,
+) -> io::Result<u64> {
+    let mut length = 0u64;
+    let mut bytes = Vec::with_capacity(BUFFER_LENGTH * 4);
+
+    if format == WavFormat::Int16 && stream.get_sample_format() == SampleFormat::Int16 {
+        let mut buffer = vec![0i16; BUFFER_LENGTH];
+
+        loop {
+            let read = stream.read_i16(&mut buffer)?;
+
+            if read == 0 {
+                break;
+            }
+
+            bytes.clear();
+            bytes.extend(
+                buffer[..read]
+                    .iter()
+                    .flat_map(|sample| sample.to_le_bytes()),
+            );
+
+            writer.write_all(&bytes)?;
+            length += bytes.len() as u64;
+        }
+    } else {
+        let mut buffer = vec![0f32; BUFFER_LENGTH];
+
+        loop {
+            let read = stream.read_f32(&mut buffer)?;
+
+            if read == 0 {
+                break;
+            }
+
+            bytes.clear();
+
+            match format {
+                WavFormat::Float => bytes.extend(
+                    buffer[..read]
+                        .iter()
+                        .flat_map(|sample| sample.to_le_bytes()),
+                ),
+                WavFormat::Int16 => bytes.extend(
+                    buffer[..read]
+                        .iter()
+                        .flat_map(|&sample| f32_to_i16(sample).to_le_bytes()),
+                ),
+            }
+
+            writer.write_all(&bytes)?;
+            length += bytes.len() as u64;
+        }
+    }
+
+    Ok(length)
+}
+
+fn channel_mask(channels: u16) -> u32 {
+    match channels {
+        1 => 0x4,
+        2 => 0x3,
+        3 => 0x7,
+        4 => 0x33,
+        5 => 0x37,
+        6 => 0x3F,
+        7 => 0x13F,
+        8 => 0x63F,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryInto;
+    use std::io::Cursor;
+
+    fn header(channels: u16, frequency: u32, options: &WavOptions, data_length: u64) -> Vec<u8> {
+        let mut cursor = Cursor::new(Vec::new());
+
+        write_wav_header(&mut cursor, channels, frequency, options, data_length).unwrap();
+
+        cursor.into_inner()
+    }
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn int16_stereo_header() {
+        let bytes = header(2, 44100, &WavOptions::default(), 1000);
+
+        let mut expected = Vec::new();
+
+        expected.extend_from_slice(b"RIFF");
+        expected.extend_from_slice(&(36u32 + 36 + 1000).to_le_bytes());
+        expected.extend_from_slice(b"WAVE");
+        expected.extend_from_slice(b"JUNK");
+        expected.extend_from_slice(&28u32.to_le_bytes());
+        expected.extend_from_slice(&[0; 28]);
+        expected.extend_from_slice(b"fmt ");
+        expected.extend_from_slice(&16u32.to_le_bytes());
+        expected.extend_from_slice(&1u16.to_le_bytes());
+        expected.extend_from_slice(&2u16.to_le_bytes());
+        expected.extend_from_slice(&44100u32.to_le_bytes());
+        expected.extend_from_slice(&176400u32.to_le_bytes());
+        expected.extend_from_slice(&4u16.to_le_bytes());
+        expected.extend_from_slice(&16u16.to_le_bytes());
+        expected.extend_from_slice(b"data");
+        expected.extend_from_slice(&1000u32.to_le_bytes());
+
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), 44 + 36);
+    }
+
+    #[test]
+    fn float_header_has_fact_chunk() {
+        let options = WavOptions {
+            format: WavFormat::Float,
+            extensible: false,
+        };
+        let bytes = header(2, 48000, &options, 8 * 1234);
+
+        assert_eq!(u32_at(&bytes, 52), 18);
+        assert_eq!(u16_at(&bytes, 56), WAVE_FORMAT_IEEE_FLOAT);
+        assert_eq!(u16_at(&bytes, 70), 32);
+        assert_eq!(u16_at(&bytes, 72), 0);
+        assert_eq!(&bytes[74..78], b"fact");
+        assert_eq!(u32_at(&bytes, 78), 4);
+        assert_eq!(u32_at(&bytes, 82), 1234);
+        assert_eq!(&bytes[86..90], b"data");
+        assert_eq!(u32_at(&bytes, 90), 8 * 1234);
+        assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8 + 8 * 1234);
+    }
+
+    #[test]
+    fn multichannel_header_is_extensible() {
+        let bytes = header(6, 48000, &WavOptions::default(), 0);
+
+        assert_eq!(u32_at(&bytes, 52), 40);
+        assert_eq!(u16_at(&bytes, 56), WAVE_FORMAT_EXTENSIBLE);
+        assert_eq!(u16_at(&bytes, 58), 6);
+        assert_eq!(u16_at(&bytes, 68), 12);
+        assert_eq!(u16_at(&bytes, 72), 22);
+        assert_eq!(u16_at(&bytes, 74), 16);
+        assert_eq!(u32_at(&bytes, 76), 0x3F);
+        assert_eq!(u32_at(&bytes, 80), WAVE_FORMAT_PCM as u32);
+        assert_eq!(&bytes[84..98], &SUBFORMAT_GUID_TAIL);
+        assert_eq!(&bytes[98..102], b"data");
+    }
+
+    #[test]
+    fn large_header_is_rf64() {
+        let data_length = u32::MAX as u64 + 1000;
+        let bytes = header(2, 44100, &WavOptions::default(), data_length);
+
+        assert_eq!(&bytes[0..4], b"RF64");
+        assert_eq!(u32_at(&bytes, 4), u32::MAX);
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(&bytes[12..16], b"ds64");
+        assert_eq!(u32_at(&bytes, 16), 28);
+        assert_eq!(u64_at(&bytes, 20), bytes.len() as u64 - 8 + data_length);
+        assert_eq!(u64_at(&bytes, 28), data_length);
+        assert_eq!(u64_at(&bytes, 36), data_length / 4);
+        assert_eq!(u32_at(&bytes, 44), 0);
+        assert_eq!(&bytes[bytes.len() - 8..bytes.len() - 4], b"data");
+        assert_eq!(u32_at(&bytes, bytes.len() - 4), u32::MAX);
+        assert_eq!(bytes.len(), 80);
+    }
+}